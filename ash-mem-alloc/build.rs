@@ -5,11 +5,219 @@ const INCLUDES: &[&str] = &[
     concat!(env!("CARGO_MANIFEST_DIR"), "/vendor/vk-headers/include"),
 ];
 
+/// VMA debug switches, given as (macro, cargo feature).
+///
+/// Each one is turned on by its feature or by setting an environment variable named like the macro to a non-zero value.
+const DEBUG_SWITCHES: &[(&str, &str)] = &[
+    ("VMA_DEBUG_DETECT_CORRUPTION", "DEBUG_DETECT_CORRUPTION"),
+    (
+        "VMA_DEBUG_INITIALIZE_ALLOCATIONS",
+        "DEBUG_INITIALIZE_ALLOCATIONS",
+    ),
+    (
+        "VMA_DEBUG_ALWAYS_DEDICATED_MEMORY",
+        "DEBUG_ALWAYS_DEDICATED_MEMORY",
+    ),
+    ("VMA_DEBUG_GLOBAL_MUTEX", "DEBUG_GLOBAL_MUTEX"),
+];
+
+/// VMA debug values that are read from an environment variable named like the macro
+const DEBUG_VALUES: &[&str] = &["VMA_DEBUG_MARGIN", "VMA_DEBUG_MIN_BUFFER_IMAGE_GRANULARITY"];
+
+/// Margin used for corruption detection if `VMA_DEBUG_MARGIN` is not set
+const DEFAULT_CORRUPTION_MARGIN: u64 = 16;
+
+/// Git submodules the bundled build needs, given as (path, file that has to exist inside of it)
+const SUBMODULES: &[(&str, &str)] = &[
+    ("vendor/vma", "include/vk_mem_alloc.h"),
+    ("vendor/vk-headers", "include/vulkan/vulkan_core.h"),
+];
+
 fn main() {
-    cc::Build::new()
+    // printing any `rerun-if` line disables cargo's default of watching the whole package
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={VMA_FILE}");
+    for include in INCLUDES {
+        println!("cargo:rerun-if-changed={include}");
+    }
+
+    // an explicitly given library always wins
+    if let Some(lib_dir) = std::env::var_os("VMA_LIB_DIR") {
+        println!("cargo:rerun-if-env-changed=VMA_LIB_DIR");
+        warn_about_build_options();
+        link_prebuilt(&lib_dir.to_string_lossy());
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=VMA_LIB_DIR");
+
+    let missing_submodules = missing_submodules();
+    if feature_enabled("BUNDLED") && missing_submodules.is_empty() {
+        compile_bundled();
+        return;
+    }
+
+    match pkg_config::probe_library("vma") {
+        Ok(_) => warn_about_build_options(),
+        Err(error) => {
+            let mut message = String::from("could not find VMA\n");
+            if feature_enabled("BUNDLED") {
+                for path in &missing_submodules {
+                    message += &format!(
+                        "- the bundled sources are missing, the git submodule `ash-mem-alloc/{path}` is empty. \
+                        Run `git submodule update --init --recursive`\n"
+                    );
+                }
+            } else {
+                message += "- the `bundled` feature is disabled\n";
+            }
+            message +=
+                "- `VMA_LIB_DIR` is not set to a directory containing a prebuilt `vma` library\n";
+            message += &format!("- pkg-config did not find `vma`: {error}\n");
+            panic!("{message}");
+        }
+    }
+}
+
+/// Compiles the vendored VMA sources, applying all configuration from features and environment variables
+fn compile_bundled() {
+    let mut build = cc::Build::new();
+    build
         .cpp(true)
         .file(VMA_FILE)
         .includes(INCLUDES)
-        .warnings(false)
-        .compile("vma");
+        .warnings(false);
+
+    for (name, value) in debug_defines() {
+        build.define(name, value.to_string().as_str());
+    }
+
+    if feature_enabled("ASSERT_HOOK") {
+        build.define("VMA_RS_ASSERT_HOOK", None);
+    }
+    if feature_enabled("HEAVY_ASSERT") {
+        build.define("VMA_RS_HEAVY_ASSERT", None);
+    }
+    if feature_enabled("LOG") || feature_enabled("TRACING") {
+        build.define("VMA_RS_DEBUG_LOG", None);
+    }
+    if feature_enabled("STATIC_VULKAN_FUNCTIONS") {
+        build.define("VMA_RS_STATIC_VULKAN_FUNCTIONS", None);
+        link_vulkan_loader();
+    }
+
+    build.compile("vma");
+}
+
+/// Links the system Vulkan loader, searching the Vulkan SDK first if `VULKAN_SDK` is set
+fn link_vulkan_loader() {
+    println!("cargo:rerun-if-env-changed=VULKAN_SDK");
+    let windows = std::env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "windows");
+
+    if let Some(sdk) = std::env::var_os("VULKAN_SDK") {
+        let lib_dir = std::path::Path::new(&sdk).join(if windows { "Lib" } else { "lib" });
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    }
+    println!(
+        "cargo:rustc-link-lib=dylib={}",
+        if windows { "vulkan-1" } else { "vulkan" }
+    );
+}
+
+/// Links the `vma` library found in `lib_dir`, statically if `VMA_STATIC` is set to a non-zero value
+fn link_prebuilt(lib_dir: &str) {
+    println!("cargo:rustc-link-search=native={lib_dir}");
+
+    if env_value("VMA_STATIC").is_some_and(|value| value != 0) {
+        println!("cargo:rustc-link-lib=static=vma");
+        // a static library does not bring its C++ runtime along
+        let target = std::env::var("TARGET").unwrap_or_default();
+        if target.contains("apple") || target.contains("freebsd") || target.contains("openbsd") {
+            println!("cargo:rustc-link-lib=c++");
+        } else if !target.contains("msvc") {
+            println!("cargo:rustc-link-lib=stdc++");
+        }
+    } else {
+        println!("cargo:rustc-link-lib=dylib=vma");
+    }
+}
+
+/// Returns the paths of all submodules needed by the bundled build that are not checked out
+fn missing_submodules() -> Vec<&'static str> {
+    let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    SUBMODULES
+        .iter()
+        .filter(|(path, file)| !manifest_dir.join(path).join(file).exists())
+        .map(|&(path, _)| path)
+        .collect()
+}
+
+/// Warns about features and environment variables that only take effect when compiling the bundled sources
+fn warn_about_build_options() {
+    let features = [
+        "ASSERT_HOOK",
+        "HEAVY_ASSERT",
+        "LOG",
+        "TRACING",
+        "DEBUG_DETECT_CORRUPTION",
+        "DEBUG_INITIALIZE_ALLOCATIONS",
+        "DEBUG_ALWAYS_DEDICATED_MEMORY",
+        "DEBUG_GLOBAL_MUTEX",
+        "STATIC_VULKAN_FUNCTIONS",
+    ];
+    if features.into_iter().any(feature_enabled) || !debug_defines().is_empty() {
+        println!(
+            "cargo:warning=VMA is linked from a prebuilt library, \
+            build options of ash-mem-alloc like the debug macros, the assert hook, debug logging or static Vulkan functions are ignored"
+        );
+    }
+}
+
+/// Collects the VMA debug macros to define from cargo features and environment variables
+fn debug_defines() -> Vec<(&'static str, u64)> {
+    let mut defines = Vec::new();
+
+    for &(name, feature) in DEBUG_SWITCHES {
+        let enabled_by_feature = feature_enabled(feature);
+        let enabled_by_env = env_value(name).is_some_and(|value| value != 0);
+        if enabled_by_feature || enabled_by_env {
+            defines.push((name, 1));
+        }
+    }
+
+    for &name in DEBUG_VALUES {
+        if let Some(value) = env_value(name) {
+            defines.push((name, value));
+        }
+    }
+
+    // corruption detection writes magic values into the margin, so it needs one
+    if defines
+        .iter()
+        .any(|&(name, _)| name == "VMA_DEBUG_DETECT_CORRUPTION")
+    {
+        match defines.iter().find(|&&(name, _)| name == "VMA_DEBUG_MARGIN") {
+            None => defines.push(("VMA_DEBUG_MARGIN", DEFAULT_CORRUPTION_MARGIN)),
+            Some(&(_, margin)) if margin == 0 || margin % 4 != 0 => panic!(
+                "VMA_DEBUG_DETECT_CORRUPTION requires VMA_DEBUG_MARGIN to be a non-zero multiple of 4, but it is {margin}"
+            ),
+            Some(_) => {}
+        }
+    }
+
+    defines
+}
+
+/// Checks whether the cargo feature with the upper snake case name `feature` is enabled
+fn feature_enabled(feature: &str) -> bool {
+    std::env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+}
+
+/// Reads the numeric environment variable `name`, telling cargo to rebuild when it changes
+fn env_value(name: &str) -> Option<u64> {
+    println!("cargo:rerun-if-env-changed={name}");
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse() {
+        Ok(value) => Some(value),
+        Err(_) => panic!("{name} must be a non-negative integer, but is \"{value}\""),
+    }
 }
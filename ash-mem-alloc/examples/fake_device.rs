@@ -0,0 +1,44 @@
+use ash::vk;
+use ash_mem_alloc::testing::{FakeDevice, MemoryTopology};
+use ash_mem_alloc::{vma, AllocatedBuffer, AllocationOptions};
+
+fn main() {
+    let topology = MemoryTopology::new()
+        .heap(256 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+        .heap(256 << 20, vk::MemoryHeapFlags::empty())
+        .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL)
+        .memory_type(
+            1,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        );
+    let device = FakeDevice::new(&topology);
+
+    let allocator = device
+        .create_allocator(&vma::AllocatorCreateInfo::default())
+        .unwrap();
+
+    let buffer = {
+        let buffer_info = vk::BufferCreateInfo::default()
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .size(1024)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC);
+        let options = AllocationOptions::new()
+            .usage(vma::MemoryUsage::AUTO)
+            .flags(vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE);
+
+        AllocatedBuffer::create(&allocator, &buffer_info, &options).unwrap()
+    };
+
+    println!(
+        "Buffer of {} bytes lives in memory type {}, heap usage: {:?}",
+        buffer.size(),
+        buffer.memory_type(),
+        [device.heap_usage(0), device.heap_usage(1)]
+    );
+
+    drop(buffer);
+    drop(allocator);
+
+    assert_eq!(device.memory_object_count(), 0);
+    assert_eq!(device.buffer_count(), 0);
+}
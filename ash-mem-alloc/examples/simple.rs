@@ -30,9 +30,8 @@ fn main() {
         let callbacks = vma::DeviceMemoryCallbacks::default()
             .allocate(Some(allocate_cb))
             .free(Some(free_cb));
-        let functions = vma::VulkanFunctions::default()
-            .get_instance_proc_addr(Some(entry.static_fn().get_instance_proc_addr))
-            .get_device_proc_addr(Some(instance.fp_v1_0().get_device_proc_addr));
+        let functions =
+            vma::VulkanFunctions::from_ash(&entry, &instance, &device, vk::API_VERSION_1_0, &[]);
         let info = vma::AllocatorCreateInfo::default()
             .device(device.handle())
             .instance(instance.handle())
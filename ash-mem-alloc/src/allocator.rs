@@ -0,0 +1,822 @@
+use std::ffi::{c_void, CStr};
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::{vma, AllocationOptions};
+
+/// Owns the raw allocator handle together with the loaders it was created from.
+///
+/// Destroys the allocator once the last [`Allocator`] clone is dropped.
+struct AllocatorInner {
+    handle: vma::Allocator,
+    instance: ash::Instance,
+    device: ash::Device,
+    physical_device: vk::PhysicalDevice,
+}
+
+impl Drop for AllocatorInner {
+    fn drop(&mut self) {
+        unsafe { vma::destroy_allocator(self.handle) };
+    }
+}
+
+/// Safe, reference-counted wrapper around a [`vma::Allocator`].
+///
+/// Cloning an `Allocator` is cheap and yields another reference to the same VMA allocator.
+/// The underlying allocator is destroyed when the last clone is dropped.
+///
+/// The methods mirror the free functions in [`vma`], minus the `allocator` parameter.
+/// Methods that only query the allocator are safe. Methods that operate on existing handles
+/// remain `unsafe`, as the allocator can not verify that those handles belong to it or are still alive.
+/// The same goes for methods taking create infos, whose pools, `p_next` chains and other raw pointers
+/// are passed to VMA unchecked. Allocations and memory type queries that need none of those have
+/// safe counterparts taking [`AllocationOptions`], like [`Allocator::find_memory_type`] and [`Allocator::allocate`].
+///
+/// With the `assert-hook` feature and the default panicking handler, an assertion VMA fails while the
+/// allocator is destroyed, e.g. because allocations or pools were leaked, panics from the `drop` of the last clone.
+/// If the thread is already unwinding at that point, the failure is only reported so the process does not abort.
+#[derive(Clone)]
+pub struct Allocator {
+    inner: Arc<AllocatorInner>,
+}
+
+impl Allocator {
+    /// Creates a new allocator for `device`.
+    ///
+    /// The `instance`, `physical_device` and `device` fields of `create_info` are overwritten
+    /// with the handles of the given loaders. Clones of `instance` and `device` are kept
+    /// for as long as the allocator lives.
+    ///
+    /// # Safety
+    /// - `instance`, `physical_device` and `device` must be valid and must outlive the returned allocator
+    ///   and every clone of it.
+    /// - All pointers inside `create_info` must be valid for the duration of this call.
+    /// - `create_info.flags` must not contain [`vma::AllocatorCreateFlags::EXTERNALLY_SYNCHRONIZED`],
+    ///   as an `Allocator` can be shared between threads.
+    pub unsafe fn new(
+        instance: &ash::Instance,
+        device: &ash::Device,
+        physical_device: vk::PhysicalDevice,
+        create_info: &vma::AllocatorCreateInfo,
+    ) -> Result<Self, vma::Error> {
+        let create_info = create_info
+            .instance(instance.handle())
+            .physical_device(physical_device)
+            .device(device.handle());
+
+        let handle = vma::create_allocator(&create_info)?;
+
+        Ok(Self {
+            inner: Arc::new(AllocatorInner {
+                handle,
+                instance: instance.clone(),
+                device: device.clone(),
+                physical_device,
+            }),
+        })
+    }
+
+    /// Returns the raw VMA handle of this allocator.
+    ///
+    /// The handle stays valid for as long as `self` or one of its clones is alive.
+    pub fn handle(&self) -> vma::Allocator {
+        self.inner.handle
+    }
+
+    /// Returns the instance this allocator was created with
+    pub fn instance(&self) -> &ash::Instance {
+        &self.inner.instance
+    }
+
+    /// Returns the device this allocator was created with
+    pub fn device(&self) -> &ash::Device {
+        &self.inner.device
+    }
+
+    /// Returns the physical device this allocator was created with
+    pub fn physical_device(&self) -> vk::PhysicalDevice {
+        self.inner.physical_device
+    }
+
+    /// See [`vma::get_allocator_info`]
+    pub fn get_allocator_info(&self) -> vma::AllocatorInfo {
+        unsafe { vma::get_allocator_info(self.handle()) }
+    }
+
+    /// See [`vma::get_physical_device_properties`]
+    pub fn get_physical_device_properties(&self) -> &vk::PhysicalDeviceProperties {
+        unsafe { &*vma::get_physical_device_properties(self.handle()) }
+    }
+
+    /// See [`vma::get_memory_properties`]
+    pub fn get_memory_properties(&self) -> &vk::PhysicalDeviceMemoryProperties {
+        unsafe { &*vma::get_memory_properties(self.handle()) }
+    }
+
+    /// See [`vma::get_memory_type_properties`]
+    ///
+    /// # Panics
+    /// Panics if `memory_type_index` is not a valid memory type index of the physical device.
+    pub fn get_memory_type_properties(&self, memory_type_index: u32) -> vk::MemoryPropertyFlags {
+        assert!(
+            memory_type_index < self.get_memory_properties().memory_type_count,
+            "memory type index {memory_type_index} out of range"
+        );
+        unsafe { vma::get_memory_type_properties(self.handle(), memory_type_index) }
+    }
+
+    /// See [`vma::set_current_frame_index`]
+    pub fn set_current_frame_index(&self, frame_index: u32) {
+        unsafe { vma::set_current_frame_index(self.handle(), frame_index) }
+    }
+
+    /// See [`vma::calculate_statistics`]
+    pub fn calculate_statistics(&self) -> vma::TotalStatistics {
+        unsafe { vma::calculate_statistics(self.handle()) }
+    }
+
+    /// See [`vma::get_heap_budgets`]
+    pub fn get_heap_budgets(&self) -> Vec<vma::Budget> {
+        unsafe { vma::get_heap_budgets(self.handle()) }
+    }
+
+    /// See [`vma::stats_string`]
+    pub fn stats_string(&self, detailed_map: bool) -> String {
+        unsafe { vma::stats_string(self.handle(), detailed_map) }
+    }
+
+    /// See [`vma::parse_stats`]
+    #[cfg(feature = "json")]
+    pub fn parse_stats(&self, detailed_map: bool) -> serde_json::Result<crate::json::Stats> {
+        unsafe { vma::parse_stats(self.handle(), detailed_map) }
+    }
+
+    /// Safe version of [`Allocator::find_memory_type_index`]
+    pub fn find_memory_type(
+        &self,
+        memory_type_bits: u32,
+        options: &AllocationOptions,
+    ) -> Result<u32, vma::Error> {
+        unsafe { self.find_memory_type_index(memory_type_bits, options.create_info()) }
+    }
+
+    /// Safe version of [`Allocator::find_memory_type_index_for_buffer_info`]
+    ///
+    /// # Panics
+    /// Panics if `buffer_create_info` has a `p_next` chain or uses [`vk::SharingMode::CONCURRENT`].
+    pub fn find_memory_type_for_buffer(
+        &self,
+        buffer_create_info: &vk::BufferCreateInfo,
+        options: &AllocationOptions,
+    ) -> Result<u32, vma::Error> {
+        crate::options::assert_plain_buffer_info(buffer_create_info);
+        unsafe {
+            self.find_memory_type_index_for_buffer_info(buffer_create_info, options.create_info())
+        }
+    }
+
+    /// Safe version of [`Allocator::find_memory_type_index_for_image_info`]
+    ///
+    /// # Panics
+    /// Panics if `image_create_info` has a `p_next` chain or uses [`vk::SharingMode::CONCURRENT`].
+    pub fn find_memory_type_for_image(
+        &self,
+        image_create_info: &vk::ImageCreateInfo,
+        options: &AllocationOptions,
+    ) -> Result<u32, vma::Error> {
+        crate::options::assert_plain_image_info(image_create_info);
+        unsafe {
+            self.find_memory_type_index_for_image_info(image_create_info, options.create_info())
+        }
+    }
+
+    /// See [`vma::find_memory_type_index`]
+    ///
+    /// # Safety
+    /// `allocation_create_info.pool` must be null or a live pool created by this allocator.
+    pub unsafe fn find_memory_type_index(
+        &self,
+        memory_type_bits: u32,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<u32, vma::Error> {
+        vma::find_memory_type_index(self.handle(), memory_type_bits, allocation_create_info)
+    }
+
+    /// See [`vma::find_memory_type_index_for_buffer_info`]
+    ///
+    /// # Safety
+    /// - `buffer_create_info` must be a valid `VkBufferCreateInfo`, including its `p_next` chain
+    ///   and queue family indices.
+    /// - `allocation_create_info.pool` must be null or a live pool created by this allocator.
+    pub unsafe fn find_memory_type_index_for_buffer_info(
+        &self,
+        buffer_create_info: &vk::BufferCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<u32, vma::Error> {
+        vma::find_memory_type_index_for_buffer_info(
+            self.handle(),
+            buffer_create_info,
+            allocation_create_info,
+        )
+    }
+
+    /// See [`vma::find_memory_type_index_for_image_info`]
+    ///
+    /// # Safety
+    /// - `image_create_info` must be a valid `VkImageCreateInfo`, including its `p_next` chain
+    ///   and queue family indices.
+    /// - `allocation_create_info.pool` must be null or a live pool created by this allocator.
+    pub unsafe fn find_memory_type_index_for_image_info(
+        &self,
+        image_create_info: &vk::ImageCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<u32, vma::Error> {
+        vma::find_memory_type_index_for_image_info(
+            self.handle(),
+            image_create_info,
+            allocation_create_info,
+        )
+    }
+
+    /// See [`vma::create_pool`]
+    ///
+    /// # Safety
+    /// `create_info.p_memory_allocate_next` must be null or point to a valid chain of structures
+    /// extending `VkMemoryAllocateInfo`, which must stay alive until the pool is destroyed.
+    pub unsafe fn create_pool(
+        &self,
+        create_info: &vma::PoolCreateInfo,
+    ) -> Result<vma::Pool, vma::Error> {
+        vma::create_pool(self.handle(), create_info)
+    }
+
+    /// See [`vma::destroy_pool`]
+    ///
+    /// # Safety
+    /// `pool` must have been created by this allocator and must not contain any allocations.
+    pub unsafe fn destroy_pool(&self, pool: vma::Pool) {
+        vma::destroy_pool(self.handle(), pool)
+    }
+
+    /// See [`vma::get_pool_statistics`]
+    ///
+    /// # Safety
+    /// `pool` must be a live pool created by this allocator.
+    pub unsafe fn get_pool_statistics(&self, pool: vma::Pool) -> vma::Statistics {
+        vma::get_pool_statistics(self.handle(), pool)
+    }
+
+    /// See [`vma::calculate_pool_statistics`]
+    ///
+    /// # Safety
+    /// `pool` must be a live pool created by this allocator.
+    pub unsafe fn calculate_pool_statistics(&self, pool: vma::Pool) -> vma::DetailedStatistics {
+        vma::calculate_pool_statistics(self.handle(), pool)
+    }
+
+    /// See [`vma::check_pool_corruption`]
+    ///
+    /// # Safety
+    /// `pool` must be a live pool created by this allocator.
+    pub unsafe fn check_pool_corruption(&self, pool: vma::Pool) -> Result<(), vma::Error> {
+        vma::check_pool_corruption(self.handle(), pool)
+    }
+
+    /// See [`vma::get_pool_name`]
+    ///
+    /// # Safety
+    /// `pool` must be a live pool created by this allocator.
+    pub unsafe fn get_pool_name(&self, pool: vma::Pool) -> Option<std::ffi::CString> {
+        vma::get_pool_name(self.handle(), pool)
+    }
+
+    /// See [`vma::set_pool_name`]
+    ///
+    /// # Safety
+    /// `pool` must be a live pool created by this allocator.
+    pub unsafe fn set_pool_name(&self, pool: vma::Pool, name: Option<&CStr>) {
+        vma::set_pool_name(self.handle(), pool, name)
+    }
+
+    /// Safe version of [`Allocator::allocate_memory`].
+    ///
+    /// The allocation must be freed with [`Allocator::free_memory`].
+    pub fn allocate(
+        &self,
+        memory_requirements: &vk::MemoryRequirements,
+        options: &AllocationOptions,
+    ) -> Result<(vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        unsafe { self.allocate_memory(memory_requirements, options.create_info()) }
+    }
+
+    /// See [`vma::allocate_memory`]
+    ///
+    /// # Safety
+    /// - `create_info.pool` must be null or a live pool created by this allocator.
+    /// - If `create_info.flags` contains [`vma::AllocationCreateFlags::USER_DATA_COPY_STRING`],
+    ///   `create_info.user_data` must be null or point to a null-terminated string.
+    pub unsafe fn allocate_memory(
+        &self,
+        memory_requirements: &vk::MemoryRequirements,
+        create_info: &vma::AllocationCreateInfo,
+    ) -> Result<(vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::allocate_memory(self.handle(), memory_requirements, create_info)
+    }
+
+    /// See [`vma::allocate_memory_pages`]
+    ///
+    /// # Panics
+    /// Panics if `memory_requirements` and `create_infos` differ in length.
+    ///
+    /// # Safety
+    /// Every element of `create_infos` must fulfill the requirements of [`Allocator::allocate_memory`].
+    pub unsafe fn allocate_memory_pages(
+        &self,
+        memory_requirements: &[vk::MemoryRequirements],
+        create_infos: &[vma::AllocationCreateInfo],
+    ) -> Result<(Vec<vma::Allocation>, Vec<vma::AllocationInfo<'_>>), vma::Error> {
+        assert_eq!(
+            memory_requirements.len(),
+            create_infos.len(),
+            "every allocation needs exactly one create info"
+        );
+        vma::allocate_memory_pages(self.handle(), memory_requirements, create_infos)
+    }
+
+    /// See [`vma::allocate_memory_for_buffer`]
+    ///
+    /// # Safety
+    /// `buffer` must be a valid buffer created from the device of this allocator.
+    pub unsafe fn allocate_memory_for_buffer(
+        &self,
+        buffer: vk::Buffer,
+        create_info: &vma::AllocationCreateInfo,
+    ) -> Result<(vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::allocate_memory_for_buffer(self.handle(), buffer, create_info)
+    }
+
+    /// See [`vma::allocate_memory_for_image`]
+    ///
+    /// # Safety
+    /// `image` must be a valid image created from the device of this allocator.
+    pub unsafe fn allocate_memory_for_image(
+        &self,
+        image: vk::Image,
+        create_info: &vma::AllocationCreateInfo,
+    ) -> Result<(vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::allocate_memory_for_image(self.handle(), image, create_info)
+    }
+
+    /// See [`vma::free_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator and must not be in use by the device.
+    pub unsafe fn free_memory(&self, allocation: vma::Allocation) {
+        vma::free_memory(self.handle(), allocation)
+    }
+
+    /// See [`vma::free_memory_pages`]
+    ///
+    /// # Safety
+    /// Every element of `allocations` must be a live allocation created by this allocator
+    /// and must not be in use by the device.
+    pub unsafe fn free_memory_pages(&self, allocations: &[vma::Allocation]) {
+        vma::free_memory_pages(self.handle(), allocations)
+    }
+
+    /// See [`vma::get_allocation_info`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn get_allocation_info(
+        &self,
+        allocation: vma::Allocation,
+    ) -> vma::AllocationInfo<'_> {
+        vma::get_allocation_info(self.handle(), allocation)
+    }
+
+    /// See [`vma::get_allocation_info_2`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn get_allocation_info_2(
+        &self,
+        allocation: vma::Allocation,
+    ) -> vma::AllocationInfo2<'_> {
+        vma::get_allocation_info_2(self.handle(), allocation)
+    }
+
+    /// See [`vma::set_allocation_user_data`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn set_allocation_user_data(
+        &self,
+        allocation: vma::Allocation,
+        user_data: *mut c_void,
+    ) {
+        vma::set_allocation_user_data(self.handle(), allocation, user_data)
+    }
+
+    /// See [`vma::set_allocation_name`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn set_allocation_name(&self, allocation: vma::Allocation, name: Option<&CStr>) {
+        vma::set_allocation_name(self.handle(), allocation, name)
+    }
+
+    /// See [`vma::get_allocation_memory_properties`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn get_allocation_memory_properties(
+        &self,
+        allocation: vma::Allocation,
+    ) -> vk::MemoryPropertyFlags {
+        vma::get_allocation_memory_properties(self.handle(), allocation)
+    }
+
+    /// See [`vma::map_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator in host visible memory.
+    pub unsafe fn map_memory(
+        &self,
+        allocation: vma::Allocation,
+    ) -> Result<*mut c_void, vma::Error> {
+        vma::map_memory(self.handle(), allocation)
+    }
+
+    /// See [`vma::unmap_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator that is currently mapped.
+    pub unsafe fn unmap_memory(&self, allocation: vma::Allocation) {
+        vma::unmap_memory(self.handle(), allocation)
+    }
+
+    /// See [`vma::flush_allocation`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn flush_allocation(
+        &self,
+        allocation: vma::Allocation,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Result<(), vma::Error> {
+        vma::flush_allocation(self.handle(), allocation, offset, size)
+    }
+
+    /// See [`vma::invalidate_allocation`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn invalidate_allocation(
+        &self,
+        allocation: vma::Allocation,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Result<(), vma::Error> {
+        vma::invalidate_allocation(self.handle(), allocation, offset, size)
+    }
+
+    /// See [`vma::flush_allocations`]
+    ///
+    /// # Safety
+    /// Every element of `allocations` must be a live allocation created by this allocator.
+    /// `offsets` and `sizes` must either be empty or have the same length as `allocations`.
+    pub unsafe fn flush_allocations(
+        &self,
+        allocations: &[vma::Allocation],
+        offsets: &[vk::DeviceSize],
+        sizes: &[vk::DeviceSize],
+    ) -> Result<(), vma::Error> {
+        vma::flush_allocations(self.handle(), allocations, offsets, sizes)
+    }
+
+    /// See [`vma::invalidate_allocations`]
+    ///
+    /// # Safety
+    /// Every element of `allocations` must be a live allocation created by this allocator.
+    /// `offsets` and `sizes` must either be empty or have the same length as `allocations`.
+    pub unsafe fn invalidate_allocations(
+        &self,
+        allocations: &[vma::Allocation],
+        offsets: &[vk::DeviceSize],
+        sizes: &[vk::DeviceSize],
+    ) -> Result<(), vma::Error> {
+        vma::invalidate_allocations(self.handle(), allocations, offsets, sizes)
+    }
+
+    /// See [`vma::copy_memory_to_allocation`]
+    ///
+    /// # Safety
+    /// `dst_allocation` must be a live, host visible allocation created by this allocator,
+    /// and `size` bytes starting at `dst_allocation_local_offset` must lie inside of it.
+    /// `size` must not be larger than `src.len()`.
+    pub unsafe fn copy_memory_to_allocation(
+        &self,
+        src: &[u8],
+        dst_allocation: vma::Allocation,
+        dst_allocation_local_offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Result<(), vma::Error> {
+        vma::copy_memory_to_allocation(
+            self.handle(),
+            src,
+            dst_allocation,
+            dst_allocation_local_offset,
+            size,
+        )
+    }
+
+    /// See [`vma::copy_allocation_to_memory`]
+    ///
+    /// # Safety
+    /// `src_allocation` must be a live, host visible allocation created by this allocator,
+    /// and `size` bytes starting at `src_allocation_local_offset` must lie inside of it.
+    /// `size` must not be larger than `dst.len()`.
+    pub unsafe fn copy_allocation_to_memory(
+        &self,
+        src_allocation: vma::Allocation,
+        src_allocation_local_offset: vk::DeviceSize,
+        dst: &mut [u8],
+        size: vk::DeviceSize,
+    ) -> Result<(), vma::Error> {
+        vma::copy_allocation_to_memory(
+            self.handle(),
+            src_allocation,
+            src_allocation_local_offset,
+            dst,
+            size,
+        )
+    }
+
+    /// See [`vma::check_corruption`]
+    pub fn check_corruption(&self, memory_type_bits: u32) -> Result<(), vma::Error> {
+        unsafe { vma::check_corruption(self.handle(), memory_type_bits) }
+    }
+
+    /// See [`vma::begin_defragmentation`]
+    ///
+    /// # Safety
+    /// `info.pool` must either be null or a live pool created by this allocator.
+    /// `info.pfn_break_callback` must be safe to call with `info.p_break_callback_user_data`
+    /// until the defragmentation has ended.
+    pub unsafe fn begin_defragmentation(
+        &self,
+        info: &vma::DefragmentationInfo,
+    ) -> Result<vma::DefragmentationContext, vma::Error> {
+        vma::begin_defragmentation(self.handle(), info)
+    }
+
+    /// See [`vma::end_defragmentation`]
+    ///
+    /// # Safety
+    /// `context` must have been returned by [`Allocator::begin_defragmentation`] on this allocator
+    /// and must not have been ended yet.
+    pub unsafe fn end_defragmentation(
+        &self,
+        context: vma::DefragmentationContext,
+    ) -> vma::DefragmentationStats {
+        vma::end_defragmentation(self.handle(), context)
+    }
+
+    /// Starts a new pass, returning the moves to perform or [`vma::PassResult::Done`]
+    /// once no more moves are possible.
+    ///
+    /// See [`vma::begin_defragmentation_pass`]
+    ///
+    /// # Safety
+    /// `context` must be a live defragmentation context of this allocator.
+    pub unsafe fn begin_defragmentation_pass(
+        &self,
+        context: vma::DefragmentationContext,
+    ) -> Result<vma::PassResult<'_>, vma::Error> {
+        vma::begin_defragmentation_pass(self.handle(), context)
+    }
+
+    /// Commits the moves in `pass_info`, including any changes made to their operations.
+    ///
+    /// Returns `true` if more passes are needed and `false` once defragmentation is complete.
+    ///
+    /// See [`vma::end_defragmentation_pass`]
+    ///
+    /// # Safety
+    /// `context` must be a live defragmentation context of this allocator
+    /// and `pass_info` must have been returned by the matching call to [`Allocator::begin_defragmentation_pass`].
+    pub unsafe fn end_defragmentation_pass(
+        &self,
+        context: vma::DefragmentationContext,
+        pass_info: &mut vma::DefragmentationPassMoveInfo,
+    ) -> Result<bool, vma::Error> {
+        vma::end_defragmentation_pass(self.handle(), context, pass_info)
+    }
+
+    /// See [`vma::bind_buffer_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator,
+    /// `buffer` must be a valid buffer created from the device of this allocator.
+    pub unsafe fn bind_buffer_memory(
+        &self,
+        allocation: vma::Allocation,
+        buffer: vk::Buffer,
+    ) -> Result<(), vma::Error> {
+        vma::bind_buffer_memory(self.handle(), allocation, buffer)
+    }
+
+    /// See [`vma::bind_buffer_memory_2`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator,
+    /// `buffer` must be a valid buffer created from the device of this allocator.
+    pub unsafe fn bind_buffer_memory_2(
+        &self,
+        allocation: vma::Allocation,
+        allocation_local_offset: vk::DeviceSize,
+        buffer: vk::Buffer,
+        next: Option<&impl vk::ExtendsBindBufferMemoryInfo>,
+    ) -> Result<(), vma::Error> {
+        vma::bind_buffer_memory_2(
+            self.handle(),
+            allocation,
+            allocation_local_offset,
+            buffer,
+            next,
+        )
+    }
+
+    /// See [`vma::bind_image_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator,
+    /// `image` must be a valid image created from the device of this allocator.
+    pub unsafe fn bind_image_memory(
+        &self,
+        allocation: vma::Allocation,
+        image: vk::Image,
+    ) -> Result<(), vma::Error> {
+        vma::bind_image_memory(self.handle(), allocation, image)
+    }
+
+    /// See [`vma::bind_image_memory_2`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator,
+    /// `image` must be a valid image created from the device of this allocator.
+    pub unsafe fn bind_image_memory_2(
+        &self,
+        allocation: vma::Allocation,
+        allocation_local_offset: vk::DeviceSize,
+        image: vk::Image,
+        next: Option<&impl vk::ExtendsBindImageMemoryInfo>,
+    ) -> Result<(), vma::Error> {
+        vma::bind_image_memory_2(
+            self.handle(),
+            allocation,
+            allocation_local_offset,
+            image,
+            next,
+        )
+    }
+
+    /// See [`vma::create_buffer`]
+    ///
+    /// # Safety
+    /// - `buffer_create_info` must be a valid `VkBufferCreateInfo`, including its `p_next` chain
+    ///   and queue family indices.
+    /// - `allocation_create_info.pool` must be null or a live pool created by this allocator.
+    /// - If `allocation_create_info.flags` contains [`vma::AllocationCreateFlags::USER_DATA_COPY_STRING`],
+    ///   `allocation_create_info.user_data` must be null or point to a null-terminated string.
+    pub unsafe fn create_buffer(
+        &self,
+        buffer_create_info: &vk::BufferCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<(vk::Buffer, vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::create_buffer(self.handle(), buffer_create_info, allocation_create_info)
+    }
+
+    /// See [`vma::create_buffer_with_alignment`]
+    ///
+    /// # Safety
+    /// Same as [`Allocator::create_buffer`].
+    pub unsafe fn create_buffer_with_alignment(
+        &self,
+        buffer_create_info: &vk::BufferCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+        min_alignment: vk::DeviceSize,
+    ) -> Result<(vk::Buffer, vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::create_buffer_with_alignment(
+            self.handle(),
+            buffer_create_info,
+            allocation_create_info,
+            min_alignment,
+        )
+    }
+
+    /// See [`vma::create_aliasing_buffer`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn create_aliasing_buffer(
+        &self,
+        allocation: vma::Allocation,
+        buffer_create_info: &vk::BufferCreateInfo,
+    ) -> Result<vk::Buffer, vma::Error> {
+        vma::create_aliasing_buffer(self.handle(), allocation, buffer_create_info)
+    }
+
+    /// See [`vma::create_aliasing_buffer_2`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn create_aliasing_buffer_2(
+        &self,
+        allocation: vma::Allocation,
+        allocation_local_offset: vk::DeviceSize,
+        buffer_create_info: &vk::BufferCreateInfo,
+    ) -> Result<vk::Buffer, vma::Error> {
+        vma::create_aliasing_buffer_2(
+            self.handle(),
+            allocation,
+            allocation_local_offset,
+            buffer_create_info,
+        )
+    }
+
+    /// See [`vma::destroy_buffer`]
+    ///
+    /// # Safety
+    /// `buffer` and `allocation` must have been created by this allocator and must not be in use by the device.
+    pub unsafe fn destroy_buffer(&self, buffer: vk::Buffer, allocation: vma::Allocation) {
+        vma::destroy_buffer(self.handle(), buffer, allocation)
+    }
+
+    /// See [`vma::create_image`]
+    ///
+    /// # Safety
+    /// - `image_create_info` must be a valid `VkImageCreateInfo`, including its `p_next` chain
+    ///   and queue family indices.
+    /// - `allocation_create_info.pool` must be null or a live pool created by this allocator.
+    /// - If `allocation_create_info.flags` contains [`vma::AllocationCreateFlags::USER_DATA_COPY_STRING`],
+    ///   `allocation_create_info.user_data` must be null or point to a null-terminated string.
+    pub unsafe fn create_image(
+        &self,
+        image_create_info: &vk::ImageCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<(vk::Image, vma::Allocation, vma::AllocationInfo<'_>), vma::Error> {
+        vma::create_image(self.handle(), image_create_info, allocation_create_info)
+    }
+
+    /// See [`vma::create_aliasing_image`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn create_aliasing_image(
+        &self,
+        allocation: vma::Allocation,
+        image_create_info: &vk::ImageCreateInfo,
+    ) -> Result<vk::Image, vma::Error> {
+        vma::create_aliasing_image(self.handle(), allocation, image_create_info)
+    }
+
+    /// See [`vma::create_aliasing_image_2`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn create_aliasing_image_2(
+        &self,
+        allocation: vma::Allocation,
+        allocation_local_offset: vk::DeviceSize,
+        image_create_info: &vk::ImageCreateInfo,
+    ) -> Result<vk::Image, vma::Error> {
+        vma::create_aliasing_image_2(
+            self.handle(),
+            allocation,
+            allocation_local_offset,
+            image_create_info,
+        )
+    }
+
+    /// See [`vma::destroy_image`]
+    ///
+    /// # Safety
+    /// `image` and `allocation` must have been created by this allocator and must not be in use by the device.
+    pub unsafe fn destroy_image(&self, image: vk::Image, allocation: vma::Allocation) {
+        vma::destroy_image(self.handle(), image, allocation)
+    }
+}
+
+impl std::fmt::Debug for Allocator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Allocator")
+            .field("handle", &self.inner.handle)
+            .field("device", &self.inner.device.handle())
+            .field("physical_device", &self.inner.physical_device)
+            .finish()
+    }
+}
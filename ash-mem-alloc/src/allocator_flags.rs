@@ -0,0 +1,298 @@
+use std::ffi::{c_void, CStr};
+
+use ash::vk;
+
+use crate::vma;
+
+/// Outcome of [`vma::AllocatorCreateFlags::detect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DetectedAllocatorFlags {
+    /// The requested flags that are valid for the device
+    pub flags: vma::AllocatorCreateFlags,
+    /// The requested flags that were dropped because their extension or feature is not enabled
+    pub unavailable: vma::AllocatorCreateFlags,
+}
+
+/// Device features that back allocator flags, collected from a feature chain
+#[derive(Debug, Clone, Copy, Default)]
+struct EnabledFeatures {
+    buffer_device_address: bool,
+    memory_priority: bool,
+    device_coherent_memory: bool,
+    maintenance5: bool,
+}
+
+impl EnabledFeatures {
+    /// Walks the `p_next` chain starting at `chain` and records the features of all known structures
+    unsafe fn from_chain(mut chain: *const c_void) -> Self {
+        let mut features = Self::default();
+        while let Some(base) = chain.cast::<vk::BaseInStructure>().as_ref() {
+            match base.s_type {
+                vk::StructureType::PHYSICAL_DEVICE_VULKAN_1_2_FEATURES => {
+                    let info = &*chain.cast::<vk::PhysicalDeviceVulkan12Features>();
+                    features.buffer_device_address |= info.buffer_device_address == vk::TRUE;
+                }
+                vk::StructureType::PHYSICAL_DEVICE_BUFFER_DEVICE_ADDRESS_FEATURES => {
+                    let info = &*chain.cast::<vk::PhysicalDeviceBufferDeviceAddressFeatures>();
+                    features.buffer_device_address |= info.buffer_device_address == vk::TRUE;
+                }
+                vk::StructureType::PHYSICAL_DEVICE_MEMORY_PRIORITY_FEATURES_EXT => {
+                    let info = &*chain.cast::<vk::PhysicalDeviceMemoryPriorityFeaturesEXT>();
+                    features.memory_priority |= info.memory_priority == vk::TRUE;
+                }
+                vk::StructureType::PHYSICAL_DEVICE_COHERENT_MEMORY_FEATURES_AMD => {
+                    let info = &*chain.cast::<vk::PhysicalDeviceCoherentMemoryFeaturesAMD>();
+                    features.device_coherent_memory |= info.device_coherent_memory == vk::TRUE;
+                }
+                vk::StructureType::PHYSICAL_DEVICE_MAINTENANCE_5_FEATURES_KHR => {
+                    let info = &*chain.cast::<vk::PhysicalDeviceMaintenance5FeaturesKHR>();
+                    features.maintenance5 |= info.maintenance5 == vk::TRUE;
+                }
+                _ => {}
+            }
+            chain = base.p_next.cast();
+        }
+        features
+    }
+}
+
+impl vma::AllocatorCreateFlags {
+    /// Flags that depend on a device extension or feature
+    fn optional() -> Self {
+        Self::KHR_DEDICATED_ALLOCATION
+            | Self::KHR_BIND_MEMORY2
+            | Self::EXT_MEMORY_BUDGET
+            | Self::AMD_DEVICE_COHERENT_MEMORY
+            | Self::BUFFER_DEVICE_ADDRESS
+            | Self::EXT_MEMORY_PRIORITY
+            | Self::KHR_MAINTENANCE4
+            | Self::KHR_MAINTENANCE5
+    }
+
+    /// Returns all flags that can be set for a device created with `api_version`,
+    /// `enabled_extensions` and `device_create_info`.
+    ///
+    /// `enabled_extensions` are the names of all instance and device extensions that were enabled.
+    /// The enabled features are taken from the `p_next` chain of `device_create_info`,
+    /// a `vk::PhysicalDeviceFeatures2` in it is followed like every other structure.
+    /// The requirements are the ones documented on each flag:
+    ///
+    /// * `KHR_DEDICATED_ALLOCATION` needs Vulkan 1.1 or `VK_KHR_dedicated_allocation` and `VK_KHR_get_memory_requirements2`
+    /// * `KHR_BIND_MEMORY2` needs Vulkan 1.1 or `VK_KHR_bind_memory2`
+    /// * `EXT_MEMORY_BUDGET` needs `VK_EXT_memory_budget`, and Vulkan 1.1 or `VK_KHR_get_physical_device_properties2`
+    /// * `AMD_DEVICE_COHERENT_MEMORY` needs `VK_AMD_device_coherent_memory` and the `device_coherent_memory` feature
+    /// * `BUFFER_DEVICE_ADDRESS` needs Vulkan 1.2 or `VK_KHR_buffer_device_address`, and the `buffer_device_address` feature
+    /// * `EXT_MEMORY_PRIORITY` needs `VK_EXT_memory_priority` and the `memory_priority` feature
+    /// * `KHR_MAINTENANCE4` needs `VK_KHR_maintenance4`
+    /// * `KHR_MAINTENANCE5` needs `VK_KHR_maintenance5` and the `maintenance5` feature
+    ///
+    /// # Safety
+    /// The `p_next` chain of `device_create_info` must be valid.
+    pub unsafe fn available(
+        api_version: u32,
+        enabled_extensions: &[&CStr],
+        device_create_info: &vk::DeviceCreateInfo<'_>,
+    ) -> Self {
+        let has_version = |major, minor| {
+            (
+                vk::api_version_major(api_version),
+                vk::api_version_minor(api_version),
+            ) >= (major, minor)
+        };
+        let has_extension = |name: &CStr| enabled_extensions.contains(&name);
+        let features = EnabledFeatures::from_chain(device_create_info.p_next);
+
+        let mut flags = Self::empty();
+        let mut add_if = |flag, available: bool| {
+            if available {
+                flags |= flag;
+            }
+        };
+
+        add_if(
+            Self::KHR_DEDICATED_ALLOCATION,
+            has_version(1, 1)
+                || (has_extension(ash::khr::dedicated_allocation::NAME)
+                    && has_extension(ash::khr::get_memory_requirements2::NAME)),
+        );
+        add_if(
+            Self::KHR_BIND_MEMORY2,
+            has_version(1, 1) || has_extension(ash::khr::bind_memory2::NAME),
+        );
+        add_if(
+            Self::EXT_MEMORY_BUDGET,
+            has_extension(ash::ext::memory_budget::NAME)
+                && (has_version(1, 1)
+                    || has_extension(ash::khr::get_physical_device_properties2::NAME)),
+        );
+        add_if(
+            Self::AMD_DEVICE_COHERENT_MEMORY,
+            has_extension(ash::amd::device_coherent_memory::NAME)
+                && features.device_coherent_memory,
+        );
+        add_if(
+            Self::BUFFER_DEVICE_ADDRESS,
+            (has_version(1, 2) || has_extension(ash::khr::buffer_device_address::NAME))
+                && features.buffer_device_address,
+        );
+        add_if(
+            Self::EXT_MEMORY_PRIORITY,
+            has_extension(ash::ext::memory_priority::NAME) && features.memory_priority,
+        );
+        add_if(
+            Self::KHR_MAINTENANCE4,
+            has_extension(ash::khr::maintenance4::NAME),
+        );
+        add_if(
+            Self::KHR_MAINTENANCE5,
+            has_extension(ash::khr::maintenance5::NAME) && features.maintenance5,
+        );
+
+        flags
+    }
+
+    /// Keeps the optional flags of `requested` that are [available](Self::available)
+    /// for the device and reports the ones that are not.
+    ///
+    /// Flags that do not depend on the device, like `EXTERNALLY_SYNCHRONIZED`, are always kept.
+    ///
+    /// # Safety
+    /// The `p_next` chain of `device_create_info` must be valid.
+    pub unsafe fn detect(
+        requested: Self,
+        api_version: u32,
+        enabled_extensions: &[&CStr],
+        device_create_info: &vk::DeviceCreateInfo<'_>,
+    ) -> DetectedAllocatorFlags {
+        let available = Self::available(api_version, enabled_extensions, device_create_info);
+        let unavailable = requested & Self::optional() & !available;
+        DetectedAllocatorFlags {
+            flags: requested & !unavailable,
+            unavailable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use ash::vk;
+
+    use crate::vma::AllocatorCreateFlags;
+
+    fn available(
+        api_version: u32,
+        extensions: &[&CStr],
+        info: &vk::DeviceCreateInfo<'_>,
+    ) -> AllocatorCreateFlags {
+        unsafe { AllocatorCreateFlags::available(api_version, extensions, info) }
+    }
+
+    #[test]
+    fn buffer_device_address_from_vulkan_1_2_features() {
+        let mut features =
+            vk::PhysicalDeviceVulkan12Features::default().buffer_device_address(true);
+        let info = vk::DeviceCreateInfo::default().push_next(&mut features);
+
+        let flags = available(vk::API_VERSION_1_2, &[], &info);
+        assert!(flags.contains(AllocatorCreateFlags::BUFFER_DEVICE_ADDRESS));
+
+        // the feature alone is not enough before Vulkan 1.2
+        let flags = available(vk::API_VERSION_1_1, &[], &info);
+        assert!(!flags.contains(AllocatorCreateFlags::BUFFER_DEVICE_ADDRESS));
+    }
+
+    #[test]
+    fn buffer_device_address_from_extension() {
+        let extensions = [ash::khr::buffer_device_address::NAME];
+        let mut features =
+            vk::PhysicalDeviceBufferDeviceAddressFeatures::default().buffer_device_address(true);
+        let info = vk::DeviceCreateInfo::default().push_next(&mut features);
+        let flags = available(vk::API_VERSION_1_1, &extensions, &info);
+        assert!(flags.contains(AllocatorCreateFlags::BUFFER_DEVICE_ADDRESS));
+
+        let info = vk::DeviceCreateInfo::default();
+        let flags = available(vk::API_VERSION_1_1, &extensions, &info);
+        assert!(!flags.contains(AllocatorCreateFlags::BUFFER_DEVICE_ADDRESS));
+    }
+
+    #[test]
+    fn features_are_found_anywhere_in_the_chain() {
+        let extensions = [
+            ash::ext::memory_priority::NAME,
+            ash::amd::device_coherent_memory::NAME,
+            ash::khr::maintenance5::NAME,
+        ];
+        let mut features2 = vk::PhysicalDeviceFeatures2::default();
+        let mut priority =
+            vk::PhysicalDeviceMemoryPriorityFeaturesEXT::default().memory_priority(true);
+        let mut coherent =
+            vk::PhysicalDeviceCoherentMemoryFeaturesAMD::default().device_coherent_memory(true);
+        let mut maintenance5 =
+            vk::PhysicalDeviceMaintenance5FeaturesKHR::default().maintenance5(true);
+        let info = vk::DeviceCreateInfo::default()
+            .push_next(&mut features2)
+            .push_next(&mut priority)
+            .push_next(&mut coherent)
+            .push_next(&mut maintenance5);
+
+        let flags = available(vk::API_VERSION_1_1, &extensions, &info);
+        assert!(flags.contains(
+            AllocatorCreateFlags::EXT_MEMORY_PRIORITY
+                | AllocatorCreateFlags::AMD_DEVICE_COHERENT_MEMORY
+                | AllocatorCreateFlags::KHR_MAINTENANCE5
+        ));
+    }
+
+    #[test]
+    fn extensions_without_features_are_unavailable() {
+        let extensions = [
+            ash::ext::memory_priority::NAME,
+            ash::amd::device_coherent_memory::NAME,
+            ash::khr::maintenance5::NAME,
+        ];
+        let mut priority = vk::PhysicalDeviceMemoryPriorityFeaturesEXT::default();
+        let info = vk::DeviceCreateInfo::default().push_next(&mut priority);
+
+        let detected = unsafe {
+            AllocatorCreateFlags::detect(
+                AllocatorCreateFlags::EXT_MEMORY_PRIORITY
+                    | AllocatorCreateFlags::AMD_DEVICE_COHERENT_MEMORY
+                    | AllocatorCreateFlags::KHR_MAINTENANCE5
+                    | AllocatorCreateFlags::EXTERNALLY_SYNCHRONIZED,
+                vk::API_VERSION_1_3,
+                &extensions,
+                &info,
+            )
+        };
+        assert_eq!(
+            detected.flags,
+            AllocatorCreateFlags::EXTERNALLY_SYNCHRONIZED
+        );
+        assert_eq!(
+            detected.unavailable,
+            AllocatorCreateFlags::EXT_MEMORY_PRIORITY
+                | AllocatorCreateFlags::AMD_DEVICE_COHERENT_MEMORY
+                | AllocatorCreateFlags::KHR_MAINTENANCE5
+        );
+    }
+
+    #[test]
+    fn memory_budget_needs_physical_device_properties_2() {
+        let info = vk::DeviceCreateInfo::default();
+
+        let flags = available(vk::API_VERSION_1_0, &[ash::ext::memory_budget::NAME], &info);
+        assert!(!flags.contains(AllocatorCreateFlags::EXT_MEMORY_BUDGET));
+
+        let extensions = [
+            ash::ext::memory_budget::NAME,
+            ash::khr::get_physical_device_properties2::NAME,
+        ];
+        let flags = available(vk::API_VERSION_1_0, &extensions, &info);
+        assert!(flags.contains(AllocatorCreateFlags::EXT_MEMORY_BUDGET));
+
+        let flags = available(vk::API_VERSION_1_1, &[ash::ext::memory_budget::NAME], &info);
+        assert!(flags.contains(AllocatorCreateFlags::EXT_MEMORY_BUDGET));
+    }
+}
@@ -0,0 +1,122 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// What happens when VMA reports a failed `VMA_ASSERT` or `VMA_HEAVY_ASSERT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum AssertHandler {
+    /// Panic in the calling thread as soon as the VMA function that failed the assertion returns
+    #[default]
+    Panic,
+    /// Report the failure and carry on
+    Log,
+    /// Report the failure and abort the process
+    Abort,
+}
+
+static HANDLER: AtomicU8 = AtomicU8::new(AssertHandler::Panic as u8);
+
+thread_local! {
+    /// First assertion failure reported on this thread that has not been raised yet
+    static PENDING: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Sets how failed VMA assertions are reported for all threads
+pub fn set_assert_handler(handler: AssertHandler) {
+    HANDLER.store(handler as u8, Ordering::Relaxed);
+}
+
+/// Returns how failed VMA assertions are currently reported
+pub fn assert_handler() -> AssertHandler {
+    match HANDLER.load(Ordering::Relaxed) {
+        1 => AssertHandler::Log,
+        2 => AssertHandler::Abort,
+        _ => AssertHandler::Panic,
+    }
+}
+
+/// Called by `VMA_ASSERT` in `vma.cpp` when an assertion fails.
+///
+/// Never unwinds into VMA, as many assertions are checked in C++ destructors.
+/// Panics are deferred until the failing call returns to Rust, see [`raise_pending`].
+#[no_mangle]
+unsafe extern "C" fn vma_rs_assert_failed(
+    expression: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    let message = format!(
+        "VMA assertion `{}` failed at {}:{line}",
+        CStr::from_ptr(expression).to_string_lossy(),
+        CStr::from_ptr(file).to_string_lossy(),
+    );
+
+    match assert_handler() {
+        AssertHandler::Panic => PENDING.with(|pending| {
+            pending.borrow_mut().get_or_insert(message);
+        }),
+        AssertHandler::Log => report(&message),
+        AssertHandler::Abort => {
+            report(&message);
+            std::process::abort();
+        }
+    }
+}
+
+/// Reports a failure at error level under the `vma` target if the `log` or `tracing` feature
+/// is enabled, prints it to stderr otherwise
+fn report(message: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(target: "vma", "{message}");
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    log::error!(target: "vma", "{message}");
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    eprintln!("{message}");
+}
+
+/// Panics with the first assertion failure VMA reported on this thread since the last call.
+///
+/// Only reports the failure if the thread is already panicking.
+pub(crate) fn raise_pending() {
+    if let Some(message) = PENDING.with(|pending| pending.borrow_mut().take()) {
+        if std::thread::panicking() {
+            report(&message);
+        } else {
+            panic!("{message}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vma;
+
+    /// Destroys a virtual block that still holds an allocation, failing VMA's leak assertion
+    fn destroy_non_empty_virtual_block() {
+        unsafe {
+            let block =
+                vma::create_virtual_block(&vma::VirtualBlockCreateInfo::default().size(1024))
+                    .unwrap();
+            vma::virtual_allocate(block, &vma::VirtualAllocationCreateInfo::default().size(64))
+                .unwrap();
+            vma::destroy_virtual_block(block);
+        }
+    }
+
+    #[test]
+    fn failed_assertions_follow_the_handler() {
+        assert_eq!(assert_handler(), AssertHandler::Panic);
+        let panic = std::panic::catch_unwind(destroy_non_empty_virtual_block).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("VMA assertion"), "{message}");
+        assert!(PENDING.with(|pending| pending.borrow().is_none()));
+
+        set_assert_handler(AssertHandler::Log);
+        let result = std::panic::catch_unwind(destroy_non_empty_virtual_block);
+        set_assert_handler(AssertHandler::Panic);
+        assert!(result.is_ok());
+        assert!(PENDING.with(|pending| pending.borrow().is_none()));
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::VecDeque;
+
+use ash::vk;
+
+use crate::{vma, Allocator};
+
+/// Usage of a single heap in one frame, as reported by [`Allocator::get_heap_budgets`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapSample {
+    /// The frame the sample was taken in
+    pub frame_index: u32,
+    /// See [`vma::Budget::usage`](crate::vma::Budget::usage)
+    pub usage: vk::DeviceSize,
+    /// See [`vma::Budget::budget`](crate::vma::Budget::budget)
+    pub budget: vk::DeviceSize,
+    /// See [`vma::Statistics::block_bytes`](crate::vma::Statistics::block_bytes)
+    pub block_bytes: vk::DeviceSize,
+    /// See [`vma::Statistics::allocation_bytes`](crate::vma::Statistics::allocation_bytes)
+    pub allocation_bytes: vk::DeviceSize,
+}
+
+impl HeapSample {
+    /// Takes a sample from the budget of a heap
+    fn new(frame_index: u32, budget: &vma::Budget) -> Self {
+        Self {
+            frame_index,
+            usage: budget.usage,
+            budget: budget.budget,
+            block_bytes: budget.statistics.block_bytes,
+            allocation_bytes: budget.statistics.allocation_bytes,
+        }
+    }
+
+    /// Returns `usage` as a fraction of `budget`, or `0.0` if the budget is unknown
+    pub fn usage_ratio(&self) -> f64 {
+        if self.budget == 0 {
+            0.0
+        } else {
+            self.usage as f64 / self.budget as f64
+        }
+    }
+}
+
+/// Direction in which a heap crossed a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossing {
+    /// The usage rose to or above the threshold
+    Above,
+    /// The usage fell below the threshold, minus the [hysteresis](BudgetMonitor::hysteresis), again
+    Below,
+}
+
+/// Passed to the callbacks of a [`BudgetMonitor`] when a heap crosses one of its thresholds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdEvent {
+    /// The heap that crossed the threshold
+    pub heap_index: u32,
+    /// The crossed threshold, as a fraction of the budget
+    pub threshold: f64,
+    /// Whether the usage rose above or fell below the threshold
+    pub crossing: Crossing,
+    /// The sample that crossed the threshold
+    pub sample: HeapSample,
+}
+
+/// Keeps a sliding window of per-heap budget samples and reports when heaps cross usage thresholds.
+///
+/// Call [`BudgetMonitor::set_current_frame_index`] once per frame instead of
+/// [`Allocator::set_current_frame_index`], it advances the frame and takes a sample of every heap.
+///
+/// ```rust ignore
+/// let mut monitor = BudgetMonitor::new(&allocator)
+///     .window(120)
+///     .threshold(0.8)
+///     .threshold(0.95)
+///     .hysteresis(0.05)
+///     .on_threshold(|event| {
+///         if event.crossing == Crossing::Above {
+///             // start evicting resources from event.heap_index
+///         }
+///     });
+///
+/// loop {
+///     monitor.set_current_frame_index(frame_index);
+///     // ...
+/// }
+/// ```
+pub struct BudgetMonitor {
+    allocator: Allocator,
+    window: usize,
+    /// Thresholds as fractions of the budget, sorted ascending
+    thresholds: Vec<f64>,
+    hysteresis: f64,
+    callbacks: Vec<ThresholdCallback>,
+    heaps: Vec<HeapHistory>,
+}
+
+/// Called by a [`BudgetMonitor`] for every crossed threshold
+type ThresholdCallback = Box<dyn FnMut(&ThresholdEvent) + Send>;
+
+/// Samples of a single heap together with the number of thresholds it currently exceeds
+#[derive(Debug, Default)]
+struct HeapHistory {
+    samples: VecDeque<HeapSample>,
+    level: usize,
+}
+
+impl HeapHistory {
+    /// Adds `sample` to the window and returns the indices of all thresholds it crossed,
+    /// in the order described at [`BudgetMonitor::set_current_frame_index`]
+    fn record(
+        &mut self,
+        sample: HeapSample,
+        window: usize,
+        thresholds: &[f64],
+        hysteresis: f64,
+    ) -> Vec<(usize, Crossing)> {
+        if self.samples.len() == window {
+            self.samples.pop_back();
+        }
+        self.samples.push_front(sample);
+
+        let ratio = sample.usage_ratio();
+        let rising = thresholds.partition_point(|&t| t <= ratio);
+        let falling = thresholds.partition_point(|&t| t - hysteresis <= ratio);
+        let level = rising.max(self.level.min(falling));
+        let crossed = if level > self.level {
+            (self.level..level)
+                .map(|index| (index, Crossing::Above))
+                .collect()
+        } else {
+            (level..self.level)
+                .rev()
+                .map(|index| (index, Crossing::Below))
+                .collect()
+        };
+        self.level = level;
+        crossed
+    }
+}
+
+impl BudgetMonitor {
+    /// Number of frames kept by default
+    pub const DEFAULT_WINDOW: usize = 60;
+
+    /// Creates a monitor for all heaps of `allocator` without any thresholds
+    pub fn new(allocator: &Allocator) -> Self {
+        let heap_count = allocator.get_memory_properties().memory_heap_count as usize;
+        Self {
+            allocator: allocator.clone(),
+            window: Self::DEFAULT_WINDOW,
+            thresholds: Vec::new(),
+            hysteresis: 0.0,
+            callbacks: Vec::new(),
+            heaps: (0..heap_count).map(|_| HeapHistory::default()).collect(),
+        }
+    }
+
+    /// Sets the number of frames for which samples are kept, at least one
+    pub fn window(mut self, frames: usize) -> Self {
+        self.window = frames.max(1);
+        for heap in &mut self.heaps {
+            heap.samples.truncate(self.window);
+        }
+        self
+    }
+
+    /// Adds a threshold as a fraction of the budget, e.g. `0.8` for 80%
+    ///
+    /// # Panics
+    /// Panics if `fraction` is NaN or infinite.
+    pub fn threshold(mut self, fraction: f64) -> Self {
+        assert!(
+            fraction.is_finite(),
+            "threshold must be a finite fraction, got {fraction}"
+        );
+        let index = self.thresholds.partition_point(|&t| t < fraction);
+        self.thresholds.insert(index, fraction);
+        self
+    }
+
+    /// Sets how far, as a fraction of the budget, the usage has to fall below a threshold
+    /// before it is reported as [`Crossing::Below`], e.g. `0.05` for 5%.
+    ///
+    /// Keeps a usage that hovers around a threshold from reporting a crossing every frame.
+    /// Defaults to `0.0`.
+    pub fn hysteresis(mut self, margin: f64) -> Self {
+        self.hysteresis = margin.max(0.0);
+        self
+    }
+
+    /// Adds a callback that is called whenever a heap crosses one of the thresholds
+    pub fn on_threshold(mut self, callback: impl FnMut(&ThresholdEvent) + Send + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Calls [`Allocator::set_current_frame_index`] and samples the budgets of all heaps.
+    ///
+    /// Callbacks are called for every threshold crossed since the previous sample,
+    /// in ascending order for rising and in descending order for falling usage.
+    pub fn set_current_frame_index(&mut self, frame_index: u32) {
+        self.allocator.set_current_frame_index(frame_index);
+        self.sample(frame_index);
+    }
+
+    /// Samples the budgets of all heaps without advancing the frame of the allocator
+    pub fn sample(&mut self, frame_index: u32) {
+        let budgets = self.allocator.get_heap_budgets();
+        for (heap_index, (heap, budget)) in self.heaps.iter_mut().zip(&budgets).enumerate() {
+            let sample = HeapSample::new(frame_index, budget);
+            let crossed = heap.record(sample, self.window, &self.thresholds, self.hysteresis);
+            for (index, crossing) in crossed {
+                let event = ThresholdEvent {
+                    heap_index: heap_index as u32,
+                    threshold: self.thresholds[index],
+                    crossing,
+                    sample,
+                };
+                for callback in &mut self.callbacks {
+                    callback(&event);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of monitored heaps
+    pub fn heap_count(&self) -> u32 {
+        self.heaps.len() as u32
+    }
+
+    /// Returns the samples of a heap in the current window, newest first
+    pub fn samples(&self, heap_index: u32) -> impl Iterator<Item = &HeapSample> {
+        self.heaps
+            .get(heap_index as usize)
+            .into_iter()
+            .flat_map(|heap| heap.samples.iter())
+    }
+
+    /// Returns the newest sample of a heap
+    pub fn latest(&self, heap_index: u32) -> Option<HeapSample> {
+        self.samples(heap_index).next().copied()
+    }
+
+    /// Returns the highest usage of a heap in the current window
+    pub fn peak_usage(&self, heap_index: u32) -> vk::DeviceSize {
+        self.samples(heap_index)
+            .map(|sample| sample.usage)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the average usage of a heap in the current window
+    pub fn average_usage(&self, heap_index: u32) -> vk::DeviceSize {
+        let (count, sum) = self
+            .samples(heap_index)
+            .fold((0, 0), |(count, sum), sample| {
+                (count + 1, sum + sample.usage)
+            });
+        sum.checked_div(count).unwrap_or(0)
+    }
+}
+
+impl std::fmt::Debug for BudgetMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BudgetMonitor")
+            .field("allocator", &self.allocator)
+            .field("window", &self.window)
+            .field("thresholds", &self.thresholds)
+            .field("hysteresis", &self.hysteresis)
+            .field("callbacks", &self.callbacks.len())
+            .field("heaps", &self.heaps)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: [f64; 2] = [0.5, 0.8];
+
+    fn budget(usage: vk::DeviceSize) -> vma::Budget {
+        vma::Budget {
+            usage,
+            budget: 1000,
+            ..Default::default()
+        }
+    }
+
+    /// Feeds one budget per frame into a fresh history and collects the crossings of every frame
+    fn crossings(hysteresis: f64, usages: &[vk::DeviceSize]) -> Vec<Vec<(usize, Crossing)>> {
+        let mut heap = HeapHistory::default();
+        usages
+            .iter()
+            .zip(0..)
+            .map(|(&usage, frame_index)| {
+                let sample = HeapSample::new(frame_index, &budget(usage));
+                heap.record(sample, 4, &THRESHOLDS, hysteresis)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn crossings_are_reported_in_order() {
+        use Crossing::*;
+
+        assert_eq!(
+            crossings(0.0, &[400, 850, 850, 700, 100, 500]),
+            [
+                vec![],
+                vec![(0, Above), (1, Above)],
+                vec![],
+                vec![(1, Below)],
+                vec![(0, Below)],
+                vec![(0, Above)],
+            ]
+        );
+    }
+
+    #[test]
+    fn hysteresis_delays_falling_crossings() {
+        use Crossing::*;
+
+        assert_eq!(
+            crossings(0.1, &[850, 750, 720, 650, 790, 800, 450, 399]),
+            [
+                vec![(0, Above), (1, Above)],
+                vec![],
+                vec![],
+                vec![(1, Below)],
+                vec![],
+                vec![(1, Above)],
+                vec![(1, Below)],
+                vec![(0, Below)],
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_budget_counts_as_unused() {
+        let mut heap = HeapHistory::default();
+        heap.record(HeapSample::new(0, &budget(900)), 4, &THRESHOLDS, 0.0);
+        let unknown = vma::Budget {
+            usage: 900,
+            ..Default::default()
+        };
+        assert_eq!(
+            heap.record(HeapSample::new(1, &unknown), 4, &THRESHOLDS, 0.0),
+            [(1, Crossing::Below), (0, Crossing::Below)]
+        );
+    }
+
+    #[test]
+    fn window_keeps_newest_samples() {
+        let mut heap = HeapHistory::default();
+        for frame_index in 0..6 {
+            heap.record(
+                HeapSample::new(frame_index, &budget(100)),
+                4,
+                &THRESHOLDS,
+                0.0,
+            );
+        }
+        let frames = heap
+            .samples
+            .iter()
+            .map(|sample| sample.frame_index)
+            .collect::<Vec<_>>();
+        assert_eq!(frames, [5, 4, 3, 2]);
+    }
+
+    #[cfg(feature = "testing")]
+    fn monitored_device() -> (crate::testing::FakeDevice, Allocator) {
+        use crate::testing::{FakeDevice, MemoryTopology};
+
+        let device = FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(10 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL),
+        );
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        (device, allocator)
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    #[should_panic(expected = "threshold must be a finite fraction")]
+    fn non_finite_thresholds_are_rejected() {
+        let (_device, allocator) = monitored_device();
+        let _ = BudgetMonitor::new(&allocator).threshold(f64::NAN);
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    fn sample_reports_crossings_of_fake_device() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::AllocationOptions;
+
+        let (_device, allocator) = monitored_device();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut monitor = BudgetMonitor::new(&allocator).threshold(0.5).on_threshold({
+            let events = events.clone();
+            move |event| events.lock().unwrap().push(*event)
+        });
+        assert_eq!(monitor.heap_count(), 1);
+
+        monitor.sample(0);
+        assert!(events.lock().unwrap().is_empty());
+
+        // without the budget extension VMA estimates the budget as 80% of the heap
+        let requirements = vk::MemoryRequirements {
+            size: 6 << 20,
+            alignment: 256,
+            memory_type_bits: 1,
+        };
+        let options = AllocationOptions::new().flags(vma::AllocationCreateFlags::DEDICATED_MEMORY);
+        let (allocation, _) = allocator.allocate(&requirements, &options).unwrap();
+        monitor.sample(1);
+
+        let sample = monitor.latest(0).unwrap();
+        assert_eq!(sample.frame_index, 1);
+        assert_eq!(sample.usage, 6 << 20);
+        assert_eq!(sample.budget, 8 << 20);
+        assert_eq!(sample.block_bytes, 6 << 20);
+        assert_eq!(sample.allocation_bytes, 6 << 20);
+        assert_eq!(
+            events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+            [ThresholdEvent {
+                heap_index: 0,
+                threshold: 0.5,
+                crossing: Crossing::Above,
+                sample,
+            }]
+        );
+
+        unsafe { allocator.free_memory(allocation) };
+        monitor.sample(2);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].crossing, Crossing::Below);
+        assert_eq!(events[0].sample.usage, 0);
+        assert_eq!(monitor.peak_usage(0), 6 << 20);
+        assert_eq!(monitor.average_usage(0), 2 << 20);
+    }
+}
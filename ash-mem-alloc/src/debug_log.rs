@@ -0,0 +1,15 @@
+use std::ffi::{c_char, CStr};
+
+/// Called by `VMA_DEBUG_LOG_FORMAT` in `vma.cpp` with every formatted debug message.
+///
+/// Messages are emitted at debug level under the `vma` target,
+/// through `tracing` if that feature is enabled and through `log` otherwise.
+#[no_mangle]
+unsafe extern "C" fn vma_rs_debug_log(message: *const c_char) {
+    let message = CStr::from_ptr(message).to_string_lossy();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(target: "vma", "{message}");
+    #[cfg(not(feature = "tracing"))]
+    log::debug!(target: "vma", "{message}");
+}
@@ -0,0 +1,405 @@
+use std::ffi::c_void;
+
+use ash::vk;
+
+use crate::{vma, Allocator};
+
+/// What to do with an allocation that VMA wants to move during defragmentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveAction {
+    /// The resource has been re-created at [`vma::DefragmentationMove::dst_tmp_allocation`]
+    /// and a copy of its contents has been recorded.
+    ///
+    /// The old resource must be destroyed once the copy has finished executing.
+    Copy,
+    /// The allocation can not be moved and stays where it is
+    Ignore,
+    /// The resource has been destroyed and its allocation is freed at the end of the pass
+    Destroy,
+}
+
+impl From<MoveAction> for vma::DefragmentationMoveOperation {
+    fn from(action: MoveAction) -> Self {
+        match action {
+            MoveAction::Copy => Self::COPY,
+            MoveAction::Ignore => Self::IGNORE,
+            MoveAction::Destroy => Self::DESTROY,
+        }
+    }
+}
+
+/// Drives the incremental defragmentation protocol of an [`Allocator`].
+///
+/// Takes care of beginning and ending the defragmentation and each of its passes,
+/// leaving only the actual moves to the caller.
+///
+/// ```rust ignore
+/// let stats = unsafe {
+///     Defragmenter::new(&allocator)
+///         .max_bytes_per_pass(64 * 1024 * 1024)
+///         .run(
+///             |m| {
+///                 // re-create the resource at m.dst_tmp_allocation and record the copy
+///                 MoveAction::Copy
+///             },
+///             |moves| {
+///                 // submit the copies, wait for them and destroy the old resources
+///             },
+///         )?
+/// };
+/// ```
+pub struct Defragmenter<'a> {
+    allocator: &'a Allocator,
+    info: vma::DefragmentationInfo,
+    break_callback: Option<BreakCallback<'a>>,
+}
+
+/// Polled by VMA through [`break_trampoline`] while it computes a pass
+type BreakCallback<'a> = Box<dyn FnMut() -> bool + 'a>;
+
+impl<'a> Defragmenter<'a> {
+    /// Creates a defragmenter for the default pools of `allocator` without any limits
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            allocator,
+            info: vma::DefragmentationInfo::default(),
+            break_callback: None,
+        }
+    }
+
+    /// See [`vma::DefragmentationInfo::flags`]
+    pub fn flags(mut self, flags: vma::DefragmentationFlags) -> Self {
+        self.info.flags = flags;
+        self
+    }
+
+    /// Defragments `pool` instead of the default pools
+    pub fn pool(mut self, pool: vma::Pool) -> Self {
+        self.info.pool = pool;
+        self
+    }
+
+    /// Limits the number of bytes copied in a single pass, `0` means no limit
+    pub fn max_bytes_per_pass(mut self, max_bytes_per_pass: vk::DeviceSize) -> Self {
+        self.info.max_bytes_per_pass = max_bytes_per_pass;
+        self
+    }
+
+    /// Limits the number of allocations moved in a single pass, `0` means no limit
+    pub fn max_allocations_per_pass(mut self, max_allocations_per_pass: u32) -> Self {
+        self.info.max_allocations_per_pass = max_allocations_per_pass;
+        self
+    }
+
+    /// Sets a callback that is polled by VMA while it computes a pass.
+    /// Returning `true` cuts the current pass short.
+    pub fn break_callback(mut self, callback: impl FnMut() -> bool + 'a) -> Self {
+        self.break_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Runs defragmentation until no more moves are possible.
+    ///
+    /// `on_move` is called for every move of a pass and decides what happens to it.
+    /// Once all moves of a pass have been decided, `end_pass` is called with them.
+    /// It must make sure all recorded copies have finished executing on the device
+    /// and destroy the old resources of moves with [`MoveAction::Copy`].
+    ///
+    /// Defragmentation is ended even if a pass fails.
+    ///
+    /// # Safety
+    /// The pool, if set, must be a live pool created by the allocator.
+    /// `on_move` must only return [`MoveAction::Copy`] or [`MoveAction::Destroy`]
+    /// after it has re-created or destroyed the resource bound to the allocation respectively.
+    pub unsafe fn run(
+        mut self,
+        mut on_move: impl FnMut(&vma::DefragmentationMove) -> MoveAction,
+        mut end_pass: impl FnMut(&[vma::DefragmentationMove]),
+    ) -> Result<vma::DefragmentationStats, vma::Error> {
+        // moved out of `self`, so that VMA can call it while `self` is borrowed by the passes
+        let mut break_callback = self.break_callback.take();
+        let mut info = self.info;
+        if let Some(callback) = &mut break_callback {
+            info.pfn_break_callback = Some(break_trampoline);
+            info.p_break_callback_user_data = callback as *mut BreakCallback<'a> as *mut c_void;
+        }
+
+        let context = self.allocator.begin_defragmentation(&info)?;
+        let result = self.run_passes(context, &mut on_move, &mut end_pass);
+        let stats = self.allocator.end_defragmentation(context);
+        result.map(|()| stats)
+    }
+
+    /// Performs passes until VMA reports that defragmentation is complete
+    unsafe fn run_passes(
+        &self,
+        context: vma::DefragmentationContext,
+        on_move: &mut impl FnMut(&vma::DefragmentationMove) -> MoveAction,
+        end_pass: &mut impl FnMut(&[vma::DefragmentationMove]),
+    ) -> Result<(), vma::Error> {
+        loop {
+            let mut pass_info = match self.allocator.begin_defragmentation_pass(context)? {
+                vma::PassResult::Done => return Ok(()),
+                vma::PassResult::Moves(pass_info) => pass_info,
+            };
+
+            for m in pass_info.get_moves_mut() {
+                m.operation = on_move(m).into();
+            }
+            end_pass(pass_info.get_moves());
+
+            if !self
+                .allocator
+                .end_defragmentation_pass(context, &mut pass_info)?
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Defragmenter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Defragmenter")
+            .field("allocator", &self.allocator)
+            .field("flags", &self.info.flags)
+            .field("pool", &self.info.pool)
+            .field("max_bytes_per_pass", &self.info.max_bytes_per_pass)
+            .field(
+                "max_allocations_per_pass",
+                &self.info.max_allocations_per_pass,
+            )
+            .field("break_callback", &self.break_callback.is_some())
+            .finish()
+    }
+}
+
+/// Forwards the break check of VMA to the boxed closure passed as user data
+unsafe extern "system" fn break_trampoline(user_data: *mut c_void) -> u32 {
+    // `'_` keeps the lifetime the callback was created with instead of assuming `'static`
+    let callback = &mut *(user_data as *mut BreakCallback<'_>);
+    callback() as u32
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::cell::RefCell;
+
+    use ash::vk;
+
+    use super::{Defragmenter, MoveAction};
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::{vma, Allocator};
+
+    const BLOCK_SIZE: vk::DeviceSize = 1 << 20;
+    const BUFFER_SIZE: vk::DeviceSize = BLOCK_SIZE / 4;
+
+    /// A buffer owned by the test whose memory is filled with `pattern`
+    struct TestBuffer {
+        buffer: vk::Buffer,
+        allocation: vma::Allocation,
+        pattern: u8,
+    }
+
+    fn device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .memory_type(
+                    0,
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL
+                        | vk::MemoryPropertyFlags::HOST_VISIBLE
+                        | vk::MemoryPropertyFlags::HOST_COHERENT,
+                ),
+        )
+    }
+
+    fn buffer_info() -> vk::BufferCreateInfo<'static> {
+        vk::BufferCreateInfo::default()
+            .size(BUFFER_SIZE)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC | vk::BufferUsageFlags::TRANSFER_DST)
+    }
+
+    /// Maps `allocation` and passes the memory of its buffer to `f`
+    unsafe fn with_contents<R>(
+        allocator: &Allocator,
+        allocation: vma::Allocation,
+        f: impl FnOnce(&mut [u8]) -> R,
+    ) -> R {
+        let data = allocator.map_memory(allocation).unwrap();
+        let result = f(std::slice::from_raw_parts_mut(
+            data.cast(),
+            BUFFER_SIZE as usize,
+        ));
+        allocator.unmap_memory(allocation);
+        result
+    }
+
+    /// Fills two blocks of a custom pool with four buffers each, then frees every other buffer
+    /// so that both blocks are half empty
+    fn fragmented_pool(device: &FakeDevice) -> (Allocator, vma::Pool, Vec<TestBuffer>) {
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let pool_info = vma::PoolCreateInfo::default()
+            .memory_type_index(0)
+            .block_size(BLOCK_SIZE);
+        let pool = unsafe { allocator.create_pool(&pool_info) }.unwrap();
+
+        let alloc_info = vma::AllocationCreateInfo::default().pool(pool);
+        let (buffers, freed): (Vec<_>, Vec<_>) = (0..8)
+            .map(|pattern| unsafe {
+                let (buffer, allocation, _) = allocator
+                    .create_buffer(&buffer_info(), &alloc_info)
+                    .unwrap();
+                with_contents(&allocator, allocation, |data| data.fill(pattern));
+                TestBuffer {
+                    buffer,
+                    allocation,
+                    pattern,
+                }
+            })
+            .partition(|buffer| buffer.pattern % 2 == 0);
+        assert_eq!(device.memory_object_count(), 2);
+
+        for buffer in freed {
+            unsafe { allocator.destroy_buffer(buffer.buffer, buffer.allocation) };
+        }
+        (allocator, pool, buffers)
+    }
+
+    /// Checks that every buffer still holds its pattern and destroys the buffers and the pool
+    fn check_and_destroy(allocator: &Allocator, pool: vma::Pool, buffers: Vec<TestBuffer>) {
+        for buffer in buffers {
+            unsafe {
+                with_contents(allocator, buffer.allocation, |data| {
+                    assert!(data.iter().all(|&byte| byte == buffer.pattern))
+                });
+                allocator.destroy_buffer(buffer.buffer, buffer.allocation);
+            }
+        }
+        unsafe { allocator.destroy_pool(pool) };
+    }
+
+    #[test]
+    fn copy_moves_free_a_block() {
+        let device = device();
+        let (allocator, pool, mut buffers) = fragmented_pool(&device);
+
+        // the old buffers are destroyed once the copies of a pass have executed
+        let stale = RefCell::new(Vec::new());
+        let stats = unsafe {
+            Defragmenter::new(&allocator).pool(pool).run(
+                |m| {
+                    let buffer = buffers
+                        .iter_mut()
+                        .find(|buffer| buffer.allocation == m.src_allocation)
+                        .unwrap();
+                    let device_fns = allocator.device();
+                    let new_buffer = device_fns.create_buffer(&buffer_info(), None).unwrap();
+                    allocator
+                        .bind_buffer_memory(m.dst_tmp_allocation, new_buffer)
+                        .unwrap();
+                    device_fns.cmd_copy_buffer(
+                        device.command_buffer(),
+                        buffer.buffer,
+                        new_buffer,
+                        &[vk::BufferCopy {
+                            src_offset: 0,
+                            dst_offset: 0,
+                            size: BUFFER_SIZE,
+                        }],
+                    );
+                    stale
+                        .borrow_mut()
+                        .push(std::mem::replace(&mut buffer.buffer, new_buffer));
+                    MoveAction::Copy
+                },
+                |moves| {
+                    assert_eq!(moves.len(), stale.borrow().len());
+                    for buffer in stale.borrow_mut().drain(..) {
+                        allocator.device().destroy_buffer(buffer, None);
+                    }
+                },
+            )
+        }
+        .unwrap();
+
+        // both buffers of one block have to move for it to become empty
+        assert!(stats.allocations_moved >= 2);
+        assert_eq!(
+            stats.bytes_moved,
+            vk::DeviceSize::from(stats.allocations_moved) * BUFFER_SIZE
+        );
+        assert_eq!(stats.device_memory_blocks_freed, 1);
+        assert_eq!(stats.bytes_freed, BLOCK_SIZE);
+        assert_eq!(device.memory_object_count(), 1);
+        assert_eq!(device.buffer_count(), 4);
+
+        check_and_destroy(&allocator, pool, buffers);
+    }
+
+    #[test]
+    fn ignored_moves_change_nothing() {
+        let device = device();
+        let (allocator, pool, buffers) = fragmented_pool(&device);
+
+        let mut offered = 0;
+        let stats = unsafe {
+            Defragmenter::new(&allocator).pool(pool).run(
+                |_| {
+                    offered += 1;
+                    MoveAction::Ignore
+                },
+                |_| {},
+            )
+        }
+        .unwrap();
+
+        assert!(offered > 0);
+        assert_eq!(stats.allocations_moved, 0);
+        assert_eq!(stats.bytes_moved, 0);
+        assert_eq!(stats.device_memory_blocks_freed, 0);
+        assert_eq!(device.memory_object_count(), 2);
+        assert_eq!(device.buffer_count(), 4);
+
+        check_and_destroy(&allocator, pool, buffers);
+    }
+
+    #[test]
+    fn destroyed_moves_free_their_allocations() {
+        let device = device();
+        let (allocator, pool, mut buffers) = fragmented_pool(&device);
+
+        let stats = unsafe {
+            Defragmenter::new(&allocator).pool(pool).run(
+                |m| {
+                    let index = buffers
+                        .iter()
+                        .position(|buffer| buffer.allocation == m.src_allocation)
+                        .unwrap();
+                    let buffer = buffers.swap_remove(index);
+                    allocator.device().destroy_buffer(buffer.buffer, None);
+                    MoveAction::Destroy
+                },
+                |_| {},
+            )
+        }
+        .unwrap();
+
+        assert!(buffers.len() < 4);
+        assert_eq!(stats.allocations_moved, 0);
+        assert_eq!(stats.bytes_moved, 0);
+        assert_eq!(
+            stats.bytes_freed,
+            vk::DeviceSize::from(stats.device_memory_blocks_freed) * BLOCK_SIZE
+        );
+        assert_eq!(
+            device.memory_object_count(),
+            2 - stats.device_memory_blocks_freed as usize
+        );
+        assert_eq!(device.buffer_count(), buffers.len());
+
+        check_and_destroy(&allocator, pool, buffers);
+    }
+}
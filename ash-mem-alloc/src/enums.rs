@@ -1,5 +1,5 @@
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags for created #VmaAllocator."]
 pub struct AllocatorCreateFlags(u32);
 impl AllocatorCreateFlags {
@@ -80,8 +80,44 @@ impl AllocatorCreateFlags {
         (self.0 & other.0) != 0
     }
 }
+impl AllocatorCreateFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("EXTERNALLY_SYNCHRONIZED", 1),
+        ("KHR_DEDICATED_ALLOCATION", 2),
+        ("KHR_BIND_MEMORY2", 4),
+        ("EXT_MEMORY_BUDGET", 8),
+        ("AMD_DEVICE_COHERENT_MEMORY", 16),
+        ("BUFFER_DEVICE_ADDRESS", 32),
+        ("EXT_MEMORY_PRIORITY", 64),
+        ("KHR_MAINTENANCE4", 128),
+        ("KHR_MAINTENANCE5", 256),
+    ];
+}
+impl ::std::fmt::Debug for AllocatorCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for AllocatorCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for AllocatorCreateFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for AllocatorCreateFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("AllocatorCreateFlags", Self::NAMES, deserializer).map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "\\brief Intended usage of the allocated memory."]
 pub struct MemoryUsage(u32);
 impl MemoryUsage {
@@ -115,8 +151,45 @@ impl MemoryUsage {
         Self(v)
     }
 }
+impl MemoryUsage {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("UNKNOWN", 0),
+        ("GPU_ONLY", 1),
+        ("CPU_ONLY", 2),
+        ("CPU_TO_GPU", 3),
+        ("GPU_TO_CPU", 4),
+        ("CPU_COPY", 5),
+        ("GPU_LAZILY_ALLOCATED", 6),
+        ("AUTO", 7),
+        ("AUTO_PREFER_DEVICE", 8),
+        ("AUTO_PREFER_HOST", 9),
+    ];
+}
+impl ::std::fmt::Debug for MemoryUsage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_value(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_value(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for MemoryUsage {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_value(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for MemoryUsage {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_value("MemoryUsage", Self::NAMES, deserializer).map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags to be passed as VmaAllocationCreateInfo::flags."]
 pub struct AllocationCreateFlags(u32);
 impl AllocationCreateFlags {
@@ -213,8 +286,53 @@ impl AllocationCreateFlags {
         (self.0 & other.0) != 0
     }
 }
+impl AllocationCreateFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("DEDICATED_MEMORY", 1),
+        ("NEVER_ALLOCATE", 2),
+        ("MAPPED", 4),
+        ("USER_DATA_COPY_STRING", 32),
+        ("UPPER_ADDRESS", 64),
+        ("DONT_BIND", 128),
+        ("WITHIN_BUDGET", 256),
+        ("CAN_ALIAS", 512),
+        ("HOST_ACCESS_SEQUENTIAL_WRITE", 1024),
+        ("HOST_ACCESS_RANDOM", 2048),
+        ("HOST_ACCESS_ALLOW_TRANSFER_INSTEAD", 4096),
+        ("STRATEGY_MIN_MEMORY", 65536),
+        ("STRATEGY_MIN_TIME", 131072),
+        ("STRATEGY_MIN_OFFSET", 262144),
+        ("STRATEGY_BEST_FIT", 65536),
+        ("STRATEGY_FIRST_FIT", 131072),
+        ("STRATEGY_MASK", 458752),
+    ];
+}
+impl ::std::fmt::Debug for AllocationCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for AllocationCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for AllocationCreateFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for AllocationCreateFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("AllocationCreateFlags", Self::NAMES, deserializer)
+            .map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags to be passed as VmaPoolCreateInfo::flags."]
 pub struct PoolCreateFlags(u32);
 impl PoolCreateFlags {
@@ -283,8 +401,38 @@ impl PoolCreateFlags {
         (self.0 & other.0) != 0
     }
 }
+impl PoolCreateFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("IGNORE_BUFFER_IMAGE_GRANULARITY", 2),
+        ("LINEAR_ALGORITHM", 4),
+        ("ALGORITHM_MASK", 4),
+    ];
+}
+impl ::std::fmt::Debug for PoolCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for PoolCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for PoolCreateFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for PoolCreateFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("PoolCreateFlags", Self::NAMES, deserializer).map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags to be passed as VmaDefragmentationInfo::flags."]
 pub struct DefragmentationFlags(u32);
 impl DefragmentationFlags {
@@ -354,8 +502,40 @@ impl DefragmentationFlags {
         (self.0 & other.0) != 0
     }
 }
+impl DefragmentationFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("FLAG_ALGORITHM_FAST", 1),
+        ("FLAG_ALGORITHM_BALANCED", 2),
+        ("FLAG_ALGORITHM_FULL", 4),
+        ("FLAG_ALGORITHM_EXTENSIVE", 8),
+        ("FLAG_ALGORITHM_MASK", 15),
+    ];
+}
+impl ::std::fmt::Debug for DefragmentationFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for DefragmentationFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for DefragmentationFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for DefragmentationFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("DefragmentationFlags", Self::NAMES, deserializer).map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Operation performed on single defragmentation move. See structure #VmaDefragmentationMove."]
 pub struct DefragmentationMoveOperation(u32);
 impl DefragmentationMoveOperation {
@@ -375,8 +555,35 @@ impl DefragmentationMoveOperation {
         Self(v)
     }
 }
+impl DefragmentationMoveOperation {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[("COPY", 0), ("IGNORE", 1), ("DESTROY", 2)];
+}
+impl ::std::fmt::Debug for DefragmentationMoveOperation {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_value(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for DefragmentationMoveOperation {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_value(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for DefragmentationMoveOperation {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_value(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for DefragmentationMoveOperation {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_value("DefragmentationMoveOperation", Self::NAMES, deserializer)
+            .map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags to be passed as VmaVirtualBlockCreateInfo::flags."]
 pub struct VirtualBlockCreateFlags(u32);
 impl VirtualBlockCreateFlags {
@@ -443,8 +650,35 @@ impl VirtualBlockCreateFlags {
         (self.0 & other.0) != 0
     }
 }
+impl VirtualBlockCreateFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[("LINEAR_ALGORITHM", 1), ("ALGORITHM_MASK", 1)];
+}
+impl ::std::fmt::Debug for VirtualBlockCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for VirtualBlockCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for VirtualBlockCreateFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for VirtualBlockCreateFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("VirtualBlockCreateFlags", Self::NAMES, deserializer)
+            .map(Self)
+    }
+}
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[doc = "Flags to be passed as VmaVirtualAllocationCreateInfo::flags."]
 pub struct VirtualAllocationCreateFlags(u32);
 impl VirtualAllocationCreateFlags {
@@ -517,3 +751,36 @@ impl VirtualAllocationCreateFlags {
         (self.0 & other.0) != 0
     }
 }
+impl VirtualAllocationCreateFlags {
+    #[doc = r" Names and values of all variants, in declaration order"]
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("UPPER_ADDRESS", 64),
+        ("STRATEGY_MIN_MEMORY", 65536),
+        ("STRATEGY_MIN_TIME", 131072),
+        ("STRATEGY_MIN_OFFSET", 262144),
+        ("STRATEGY_MASK", 458752),
+    ];
+}
+impl ::std::fmt::Debug for VirtualAllocationCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+impl ::std::fmt::Display for VirtualAllocationCreateFlags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        crate::names::fmt_flags(f, self.0, Self::NAMES)
+    }
+}
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for VirtualAllocationCreateFlags {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::names::serialize_flags(self.0, Self::NAMES, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for VirtualAllocationCreateFlags {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::names::deserialize_flags("VirtualAllocationCreateFlags", Self::NAMES, deserializer)
+            .map(Self)
+    }
+}
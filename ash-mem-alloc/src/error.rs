@@ -0,0 +1,348 @@
+use ash::vk;
+
+use crate::vma;
+
+/// Error returned by fallible VMA functions.
+///
+/// Records which VMA function failed, the [`vk::Result`] it returned
+/// and, where available, what was requested from it.
+///
+/// Converts into a [`vk::Result`], so `?` keeps working in functions returning `Result<_, vk::Result>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    function: &'static str,
+    result: vk::Result,
+    size: Option<vk::DeviceSize>,
+    memory_type_bits: Option<u32>,
+    memory_type_index: Option<u32>,
+    pool: Option<vma::Pool>,
+    usage: Option<vma::MemoryUsage>,
+    flags: Option<vma::AllocationCreateFlags>,
+    extent: Option<vk::Extent3D>,
+    format: Option<vk::Format>,
+    image_usage: Option<vk::ImageUsageFlags>,
+}
+
+impl Error {
+    /// Creates a new error for a call to the VMA function `function` that returned `result`
+    pub fn new(function: &'static str, result: vk::Result) -> Self {
+        Self {
+            function,
+            result,
+            size: None,
+            memory_type_bits: None,
+            memory_type_index: None,
+            pool: None,
+            usage: None,
+            flags: None,
+            extent: None,
+            format: None,
+            image_usage: None,
+        }
+    }
+
+    /// Records the requested size in bytes, `vk::WHOLE_SIZE` standing for the rest of the allocation
+    pub fn with_size(mut self, size: vk::DeviceSize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Records the memory type bits the request was restricted to
+    pub fn with_memory_type_bits(mut self, memory_type_bits: u32) -> Self {
+        self.memory_type_bits = Some(memory_type_bits);
+        self
+    }
+
+    /// Records the memory type index the request was made for
+    pub fn with_memory_type_index(mut self, memory_type_index: u32) -> Self {
+        self.memory_type_index = Some(memory_type_index);
+        self
+    }
+
+    /// Records the pool the request was made from, ignoring null handles
+    pub fn with_pool(mut self, pool: vma::Pool) -> Self {
+        if pool != vma::Pool::default() {
+            self.pool = Some(pool);
+        }
+        self
+    }
+
+    /// Records the usage, flags and pool of an allocation request
+    pub fn with_allocation_create_info(mut self, info: &vma::AllocationCreateInfo) -> Self {
+        self.usage = Some(info.usage);
+        self.flags = Some(info.flags);
+        self.with_pool(info.pool)
+    }
+
+    /// Records size and memory type bits of an allocation request
+    pub fn with_memory_requirements(self, requirements: &vk::MemoryRequirements) -> Self {
+        self.with_size(requirements.size)
+            .with_memory_type_bits(requirements.memory_type_bits)
+    }
+
+    /// Records the size of a requested buffer
+    pub fn with_buffer_create_info(self, info: &vk::BufferCreateInfo) -> Self {
+        self.with_size(info.size)
+    }
+
+    /// Records the extent, format and usage of a requested image
+    pub fn with_image_create_info(mut self, info: &vk::ImageCreateInfo) -> Self {
+        self.extent = Some(info.extent);
+        self.format = Some(info.format);
+        self.image_usage = Some(info.usage);
+        self
+    }
+
+    /// Records the memory type of a requested pool
+    pub fn with_pool_create_info(self, info: &vma::PoolCreateInfo) -> Self {
+        self.with_memory_type_index(info.memory_type_index)
+    }
+
+    /// Records the size of a requested virtual allocation
+    pub fn with_virtual_allocation_create_info(
+        self,
+        info: &vma::VirtualAllocationCreateInfo,
+    ) -> Self {
+        self.with_size(info.size)
+    }
+
+    /// Returns the name of the VMA function that failed, e.g. `"vmaCreateBuffer"`
+    pub fn function(&self) -> &'static str {
+        self.function
+    }
+
+    /// Returns the result returned by the failed VMA function
+    pub fn result(&self) -> vk::Result {
+        self.result
+    }
+
+    /// Returns the requested size in bytes, if known.
+    ///
+    /// Flushes and invalidations of the rest of an allocation report `vk::WHOLE_SIZE`.
+    pub fn size(&self) -> Option<vk::DeviceSize> {
+        self.size
+    }
+
+    /// Returns the memory type bits the request was restricted to, if known
+    pub fn memory_type_bits(&self) -> Option<u32> {
+        self.memory_type_bits
+    }
+
+    /// Returns the memory type index the request was made for, if known
+    pub fn memory_type_index(&self) -> Option<u32> {
+        self.memory_type_index
+    }
+
+    /// Returns the pool the request was made from, if any
+    pub fn pool(&self) -> Option<vma::Pool> {
+        self.pool
+    }
+
+    /// Returns the requested memory usage, if known
+    pub fn usage(&self) -> Option<vma::MemoryUsage> {
+        self.usage
+    }
+
+    /// Returns the requested allocation flags, if known
+    pub fn flags(&self) -> Option<vma::AllocationCreateFlags> {
+        self.flags
+    }
+
+    /// Returns the extent of the requested image, if known
+    pub fn extent(&self) -> Option<vk::Extent3D> {
+        self.extent
+    }
+
+    /// Returns the format of the requested image, if known
+    pub fn format(&self) -> Option<vk::Format> {
+        self.format
+    }
+
+    /// Returns the usage of the requested image, if known
+    pub fn image_usage(&self) -> Option<vk::ImageUsageFlags> {
+        self.image_usage
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.function, self.result)?;
+
+        let mut context = Vec::new();
+        match self.size {
+            Some(vk::WHOLE_SIZE) => context.push("size: whole allocation".to_owned()),
+            Some(size) => context.push(format!("size: {size}")),
+            None => {}
+        }
+        if let Some(extent) = self.extent {
+            context.push(format!(
+                "extent: {}x{}x{}",
+                extent.width, extent.height, extent.depth
+            ));
+        }
+        if let Some(format) = self.format {
+            context.push(format!("format: {format:?}"));
+        }
+        if let Some(usage) = self.image_usage {
+            context.push(format!("image usage: {usage:?}"));
+        }
+        if let Some(bits) = self.memory_type_bits {
+            context.push(format!("memory type bits: {bits:#b}"));
+        }
+        if let Some(index) = self.memory_type_index {
+            context.push(format!("memory type index: {index}"));
+        }
+        if let Some(pool) = self.pool {
+            context.push(format!("pool: {pool}"));
+        }
+        if let Some(usage) = self.usage {
+            context.push(format!("usage: {usage:?}"));
+        }
+        if let Some(flags) = self.flags {
+            context.push(format!("flags: {flags:?}"));
+        }
+
+        if !context.is_empty() {
+            write!(f, " ({})", context.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// the `vk::Result` is already part of the message, so it is not reported as the source
+impl std::error::Error for Error {}
+
+impl From<Error> for vk::Result {
+    fn from(error: Error) -> Self {
+        error.result
+    }
+}
+
+impl PartialEq<vk::Result> for Error {
+    fn eq(&self, other: &vk::Result) -> bool {
+        self.result == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_without_context() {
+        let error = Error::new("vmaCreatePool", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "vmaCreatePool failed: {}",
+                vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+            )
+        );
+    }
+
+    #[test]
+    fn display_lists_context_in_order() {
+        let requirements = vk::MemoryRequirements {
+            size: 1024,
+            alignment: 256,
+            memory_type_bits: 0b101,
+        };
+        let create_info = vma::AllocationCreateInfo::default()
+            .usage(vma::MemoryUsage::AUTO)
+            .flags(vma::AllocationCreateFlags::MAPPED);
+        let error = Error::new("vmaAllocateMemory", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+            .with_memory_requirements(&requirements)
+            .with_allocation_create_info(&create_info);
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "vmaAllocateMemory failed: {} (size: 1024, memory type bits: 0b101, usage: AUTO, flags: MAPPED)",
+                vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+            )
+        );
+    }
+
+    #[test]
+    fn display_image_context() {
+        let image_info = vk::ImageCreateInfo::default()
+            .extent(vk::Extent3D {
+                width: 64,
+                height: 32,
+                depth: 1,
+            })
+            .format(vk::Format::R8G8B8A8_UNORM)
+            .usage(vk::ImageUsageFlags::SAMPLED);
+        let error = Error::new("vmaCreateImage", vk::Result::ERROR_FEATURE_NOT_PRESENT)
+            .with_image_create_info(&image_info);
+
+        assert!(error
+            .to_string()
+            .ends_with("(extent: 64x32x1, format: R8G8B8A8_UNORM, image usage: SAMPLED)"));
+    }
+
+    #[test]
+    fn display_whole_size() {
+        let error = Error::new("vmaFlushAllocation", vk::Result::ERROR_DEVICE_LOST)
+            .with_size(vk::WHOLE_SIZE);
+        assert_eq!(error.size(), Some(vk::WHOLE_SIZE));
+        assert!(error.to_string().ends_with("(size: whole allocation)"));
+    }
+
+    #[test]
+    fn converts_into_vk_result() {
+        fn create() -> Result<(), vk::Result> {
+            Err(Error::new(
+                "vmaCreateBuffer",
+                vk::Result::ERROR_OUT_OF_HOST_MEMORY,
+            ))?;
+            Ok(())
+        }
+
+        assert_eq!(create(), Err(vk::Result::ERROR_OUT_OF_HOST_MEMORY));
+        let error = Error::new("vmaCreateBuffer", vk::Result::ERROR_OUT_OF_HOST_MEMORY);
+        assert_eq!(
+            vk::Result::from(error),
+            vk::Result::ERROR_OUT_OF_HOST_MEMORY
+        );
+        assert_eq!(error, vk::Result::ERROR_OUT_OF_HOST_MEMORY);
+    }
+
+    #[test]
+    fn builders_record_context() {
+        let error = Error::new("vmaCreateBuffer", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+            .with_buffer_create_info(&vk::BufferCreateInfo::default().size(4096))
+            .with_memory_type_index(3);
+        assert_eq!(error.function(), "vmaCreateBuffer");
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(error.size(), Some(4096));
+        assert_eq!(error.memory_type_index(), Some(3));
+        assert_eq!(error.memory_type_bits(), None);
+        assert_eq!(error.usage(), None);
+        assert_eq!(error.extent(), None);
+
+        let error = Error::new("vmaCreatePool", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+            .with_pool_create_info(&vma::PoolCreateInfo::default().memory_type_index(1));
+        assert_eq!(error.memory_type_index(), Some(1));
+
+        let error = Error::new("vmaVirtualAllocate", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+            .with_virtual_allocation_create_info(
+                &vma::VirtualAllocationCreateInfo::default().size(96),
+            );
+        assert_eq!(error.size(), Some(96));
+    }
+
+    #[test]
+    fn null_pools_are_not_recorded() {
+        let error = Error::new("vmaAllocateMemory", vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+            .with_allocation_create_info(&vma::AllocationCreateInfo::default());
+        assert_eq!(error.pool(), None);
+        assert_eq!(error.usage(), Some(vma::MemoryUsage::UNKNOWN));
+        assert_eq!(error.flags(), Some(vma::AllocationCreateFlags::empty()));
+
+        let pool = vma::Pool::from_raw(0x1000 as _);
+        let error = error.with_pool(pool);
+        assert_eq!(error.pool(), Some(pool));
+        assert!(error.to_string().contains(&format!("pool: {pool}")));
+    }
+}
@@ -0,0 +1,612 @@
+//! Typed model of the JSON dump built by [`vma::build_stats_string`](crate::vma::build_stats_string).
+//!
+//! [`vma::parse_stats`](crate::vma::parse_stats) and [`Allocator::parse_stats`](crate::Allocator::parse_stats)
+//! build the dump and parse it into [`Stats`].
+//! The dump can also be parsed directly, e.g. after it was written to a file:
+//!
+//! ```no_run
+//! # fn load(dump: &str) -> serde_json::Result<()> {
+//! let stats: ash_mem_alloc::json::Stats = serde_json::from_str(dump)?;
+//! for (index, heap) in stats.heaps() {
+//!     println!("heap {index}: {} of {} bytes used", heap.stats.allocation_bytes, heap.size);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Objects that VMA keys by name, like `"Heap 0"` or `"Type 3"`, are kept as maps with the original keys.
+//! Helpers like [`Stats::heaps`] and [`Stats::heap`] look them up by index.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The whole dump
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Stats {
+    /// Information about the device and the allocator
+    pub general: General,
+    /// Statistics over all heaps
+    pub total: DetailedStatistics,
+    /// All memory heaps, keyed by `"Heap <index>"`
+    pub memory_info: BTreeMap<String, Heap>,
+    /// The blocks of the default pools, keyed by `"Type <index>"`.
+    /// Only present in a detailed map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_pools: Option<BTreeMap<String, DefaultPool>>,
+    /// The custom pools, keyed by `"Type <index>"` of their memory type.
+    /// Only present in a detailed map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_pools: Option<BTreeMap<String, Vec<CustomPool>>>,
+}
+
+impl Stats {
+    /// Returns the heap with the given index
+    pub fn heap(&self, heap_index: u32) -> Option<&Heap> {
+        self.memory_info.get(&format!("Heap {heap_index}"))
+    }
+
+    /// Returns all heaps together with their index, ordered by index
+    pub fn heaps(&self) -> Vec<(u32, &Heap)> {
+        indexed(&self.memory_info, "Heap ")
+    }
+
+    /// Returns the memory type with the given index, searching all heaps
+    pub fn memory_type(&self, memory_type_index: u32) -> Option<&MemoryType> {
+        let key = format!("Type {memory_type_index}");
+        self.memory_info
+            .values()
+            .find_map(|heap| heap.memory_pools.get(&key))
+    }
+
+    /// Returns the default pool of the given memory type, if the dump contains a detailed map
+    pub fn default_pool(&self, memory_type_index: u32) -> Option<&DefaultPool> {
+        self.default_pools
+            .as_ref()?
+            .get(&format!("Type {memory_type_index}"))
+    }
+
+    /// Returns the custom pools of the given memory type, if the dump contains a detailed map
+    pub fn custom_pools(&self, memory_type_index: u32) -> &[CustomPool] {
+        self.custom_pools
+            .as_ref()
+            .and_then(|pools| pools.get(&format!("Type {memory_type_index}")))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// The `General` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct General {
+    /// Always `"Vulkan"`
+    #[serde(rename = "API")]
+    pub api: String,
+    /// The Vulkan version the allocator uses, formatted as `major.minor.patch`
+    pub api_version: String,
+    /// The device name
+    #[serde(rename = "GPU")]
+    pub gpu: String,
+    /// The raw `vk::PhysicalDeviceType`
+    pub device_type: i32,
+    pub max_memory_allocation_count: u32,
+    pub buffer_image_granularity: u64,
+    pub non_coherent_atom_size: u64,
+    pub memory_heap_count: u32,
+    pub memory_type_count: u32,
+}
+
+/// Mirrors [`vma::DetailedStatistics`](crate::vma::DetailedStatistics).
+///
+/// VMA leaves out the size ranges if there are fewer than two allocations or unused ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DetailedStatistics {
+    pub block_count: u32,
+    pub block_bytes: u64,
+    pub allocation_count: u32,
+    pub allocation_bytes: u64,
+    pub unused_range_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocation_size_min: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocation_size_max: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unused_range_size_min: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unused_range_size_max: Option<u64>,
+}
+
+/// Mirrors the usage and budget parts of [`vma::Budget`](crate::vma::Budget)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Budget {
+    pub budget_bytes: u64,
+    pub usage_bytes: u64,
+}
+
+/// A flag of a heap or memory type.
+///
+/// Known bits are written by name, e.g. `"DEVICE_LOCAL"`, all remaining bits as one number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Flag {
+    Name(String),
+    Bits(u32),
+}
+
+/// A memory heap in the `MemoryInfo` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Heap {
+    pub flags: Vec<Flag>,
+    pub size: u64,
+    pub budget: Budget,
+    pub stats: DetailedStatistics,
+    /// The memory types of the heap, keyed by `"Type <index>"`
+    pub memory_pools: BTreeMap<String, MemoryType>,
+}
+
+impl Heap {
+    /// Returns the memory types of the heap together with their index, ordered by index
+    pub fn memory_types(&self) -> Vec<(u32, &MemoryType)> {
+        indexed(&self.memory_pools, "Type ")
+    }
+}
+
+/// A memory type of a [`Heap`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MemoryType {
+    pub flags: Vec<Flag>,
+    pub stats: DetailedStatistics,
+}
+
+/// The default pool of a memory type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DefaultPool {
+    pub preferred_block_size: u64,
+    /// The blocks of the pool, keyed by their id
+    #[serde(default)]
+    pub blocks: BTreeMap<String, Block>,
+    #[serde(default)]
+    pub dedicated_allocations: Vec<Allocation>,
+}
+
+/// A custom pool created with [`vma::create_pool`](crate::vma::create_pool).
+///
+/// VMA writes the index and the name of the pool as a single `"<index>"` or `"<index> - <name>"` string,
+/// which is split into [`CustomPool::index`] and [`CustomPool::name`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawCustomPool", into = "RawCustomPool")]
+pub struct CustomPool {
+    /// The position of the pool among the custom pools of its memory type when the dump was built.
+    ///
+    /// This is not a stable id, it changes as pools before this one are destroyed.
+    pub index: u64,
+    /// The name set with [`vma::set_pool_name`](crate::vma::set_pool_name)
+    pub name: Option<String>,
+    pub preferred_block_size: u64,
+    /// The blocks of the pool, keyed by their id
+    pub blocks: BTreeMap<String, Block>,
+    pub dedicated_allocations: Vec<Allocation>,
+}
+
+/// [`CustomPool`] as written by VMA
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawCustomPool {
+    name: String,
+    preferred_block_size: u64,
+    #[serde(default)]
+    blocks: BTreeMap<String, Block>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dedicated_allocations: Vec<Allocation>,
+}
+
+impl TryFrom<RawCustomPool> for CustomPool {
+    type Error = String;
+
+    fn try_from(raw: RawCustomPool) -> Result<Self, Self::Error> {
+        let (index, name) = match raw.name.split_once(" - ") {
+            Some((index, name)) => (index, Some(name.to_owned())),
+            None => (raw.name.as_str(), None),
+        };
+        let index = index
+            .parse()
+            .map_err(|_| format!("invalid custom pool name `{}`", raw.name))?;
+        Ok(Self {
+            index,
+            name,
+            preferred_block_size: raw.preferred_block_size,
+            blocks: raw.blocks,
+            dedicated_allocations: raw.dedicated_allocations,
+        })
+    }
+}
+
+impl From<CustomPool> for RawCustomPool {
+    fn from(pool: CustomPool) -> Self {
+        let name = match pool.name {
+            Some(name) => format!("{} - {name}", pool.index),
+            None => pool.index.to_string(),
+        };
+        Self {
+            name,
+            preferred_block_size: pool.preferred_block_size,
+            blocks: pool.blocks,
+            dedicated_allocations: pool.dedicated_allocations,
+        }
+    }
+}
+
+/// The detailed map of a single `vk::DeviceMemory` block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Block {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_ref_count: Option<u32>,
+    pub total_bytes: u64,
+    pub unused_bytes: u64,
+    /// The number of allocations in the block
+    pub allocations: u64,
+    /// The number of free ranges in the block
+    pub unused_ranges: u64,
+    /// All allocations and free ranges, ordered by offset
+    pub suballocations: Vec<Allocation>,
+}
+
+impl Block {
+    /// Iterates over the allocations of the block, leaving out free ranges
+    pub fn allocations(&self) -> impl Iterator<Item = &Allocation> {
+        self.suballocations.iter().filter(|range| !range.is_free())
+    }
+
+    /// Iterates over the free ranges of the block
+    pub fn free_ranges(&self) -> impl Iterator<Item = &Allocation> {
+        self.suballocations.iter().filter(|range| range.is_free())
+    }
+}
+
+/// An allocation or a free range.
+///
+/// Dedicated allocations do not have an offset, free ranges only have an offset, a type and a size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Allocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// `"FREE"`, `"UNKNOWN"`, `"BUFFER"`, `"IMAGE_UNKNOWN"`, `"IMAGE_LINEAR"` or `"IMAGE_OPTIMAL"`
+    #[serde(rename = "Type")]
+    pub kind: String,
+    pub size: u64,
+    /// The raw buffer or image usage flags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<u64>,
+    /// The user data pointer, formatted as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_data: Option<String>,
+    /// The name set with [`vma::set_allocation_name`](crate::vma::set_allocation_name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl Allocation {
+    /// Checks whether this is a free range instead of an allocation
+    pub fn is_free(&self) -> bool {
+        self.kind == "FREE"
+    }
+}
+
+/// Collects the entries of `map` whose key is `prefix` followed by an index, ordered by index
+fn indexed<'a, T>(map: &'a BTreeMap<String, T>, prefix: &str) -> Vec<(u32, &'a T)> {
+    let mut entries: Vec<_> = map
+        .iter()
+        .filter_map(|(key, value)| Some((key.strip_prefix(prefix)?.parse().ok()?, value)))
+        .collect();
+    entries.sort_by_key(|&(index, _)| index);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Output of `vmaBuildStatsString` with `detailed_map = true` for a device with a
+    /// device-local and a host-visible heap, a buffer and an image in a default pool,
+    /// a named custom pool holding a buffer, and an unnamed custom pool with a dedicated allocation
+    const DETAILED_DUMP: &str = r#"{
+ "General": {
+  "API": "Vulkan",
+  "apiVersion": "1.3.280",
+  "GPU": "AMD Radeon RX 6800",
+  "deviceType": 2,
+  "maxMemoryAllocationCount": 4096,
+  "bufferImageGranularity": 1,
+  "nonCoherentAtomSize": 64,
+  "memoryHeapCount": 2,
+  "memoryTypeCount": 2
+ },
+ "Total": {
+  "BlockCount": 3,
+  "BlockBytes": 33816576,
+  "AllocationCount": 4,
+  "AllocationBytes": 1376256,
+  "UnusedRangeCount": 1,
+  "AllocationSizeMin": 65536,
+  "AllocationSizeMax": 1048576
+ },
+ "MemoryInfo": {
+  "Heap 0": {
+   "Flags": ["DEVICE_LOCAL", "MULTI_INSTANCE"],
+   "Size": 17163091968,
+   "Budget": {
+    "BudgetBytes": 16307453132,
+    "UsageBytes": 33554432
+   },
+   "Stats": {
+    "BlockCount": 1,
+    "BlockBytes": 33554432,
+    "AllocationCount": 2,
+    "AllocationBytes": 1114112,
+    "UnusedRangeCount": 1,
+    "AllocationSizeMin": 65536,
+    "AllocationSizeMax": 1048576
+   },
+   "MemoryPools": {
+    "Type 0": {
+     "Flags": ["DEVICE_LOCAL"],
+     "Stats": {
+      "BlockCount": 1,
+      "BlockBytes": 33554432,
+      "AllocationCount": 2,
+      "AllocationBytes": 1114112,
+      "UnusedRangeCount": 1,
+      "AllocationSizeMin": 65536,
+      "AllocationSizeMax": 1048576
+     }
+    }
+   }
+  },
+  "Heap 1": {
+   "Flags": [],
+   "Size": 33419145216,
+   "Budget": {
+    "BudgetBytes": 31748187955,
+    "UsageBytes": 262144
+   },
+   "Stats": {
+    "BlockCount": 2,
+    "BlockBytes": 262144,
+    "AllocationCount": 2,
+    "AllocationBytes": 262144,
+    "UnusedRangeCount": 0,
+    "AllocationSizeMin": 65536,
+    "AllocationSizeMax": 196608
+   },
+   "MemoryPools": {
+    "Type 1": {
+     "Flags": ["HOST_VISIBLE", "HOST_COHERENT", 256],
+     "Stats": {
+      "BlockCount": 2,
+      "BlockBytes": 262144,
+      "AllocationCount": 2,
+      "AllocationBytes": 262144,
+      "UnusedRangeCount": 0,
+      "AllocationSizeMin": 65536,
+      "AllocationSizeMax": 196608
+     }
+    }
+   }
+  }
+ },
+ "DefaultPools": {
+  "Type 0": {
+   "PreferredBlockSize": 268435456,
+   "Blocks": {
+    "0": {
+     "MapRefCount": 0,
+     "TotalBytes": 33554432,
+     "UnusedBytes": 32440320,
+     "Allocations": 2,
+     "UnusedRanges": 1,
+     "Suballocations": [
+      {"Offset": 0, "Type": "BUFFER", "Size": 1048576, "Usage": 130, "CustomData": "0000000000000000", "Name": "vertices"},
+      {"Offset": 1048576, "Type": "IMAGE_OPTIMAL", "Size": 65536, "Usage": 6},
+      {"Offset": 1114112, "Type": "FREE", "Size": 32440320}
+     ]
+    }
+   },
+   "DedicatedAllocations": []
+  },
+  "Type 1": {
+   "PreferredBlockSize": 268435456,
+   "Blocks": {},
+   "DedicatedAllocations": []
+  }
+ },
+ "CustomPools": {
+  "Type 1": [
+   {
+    "Name": "0 - staging",
+    "PreferredBlockSize": 65536,
+    "Blocks": {
+     "1": {
+      "MapRefCount": 1,
+      "TotalBytes": 65536,
+      "UnusedBytes": 0,
+      "Allocations": 1,
+      "UnusedRanges": 0,
+      "Suballocations": [
+       {"Offset": 0, "Type": "BUFFER", "Size": 65536, "Usage": 1, "CustomData": "000001C2A5F3B0D0"}
+      ]
+     }
+    }
+   },
+   {
+    "Name": "1",
+    "PreferredBlockSize": 65536,
+    "Blocks": {},
+    "DedicatedAllocations": [
+     {"Type": "BUFFER", "Size": 196608, "Usage": 2}
+    ]
+   }
+  ]
+ }
+}"#;
+
+    #[test]
+    fn parses_detailed_dump() {
+        let stats: Stats = serde_json::from_str(DETAILED_DUMP).unwrap();
+
+        assert_eq!(stats.general.memory_heap_count, 2);
+        assert_eq!(stats.total.allocation_count, 4);
+        let heaps = stats.heaps();
+        assert_eq!(heaps.len(), 2);
+        assert_eq!(heaps[1].1.size, 33419145216);
+        assert_eq!(
+            stats.memory_type(1).unwrap().flags,
+            [
+                Flag::Name("HOST_VISIBLE".into()),
+                Flag::Name("HOST_COHERENT".into()),
+                Flag::Bits(256)
+            ]
+        );
+
+        let default_pool = stats.default_pool(0).unwrap();
+        let block = &default_pool.blocks["0"];
+        assert_eq!(block.map_ref_count, Some(0));
+        let allocations = block.allocations().collect::<Vec<_>>();
+        assert_eq!(allocations.len(), 2);
+        assert_eq!(allocations[0].name.as_deref(), Some("vertices"));
+        assert_eq!(allocations[1].kind, "IMAGE_OPTIMAL");
+        let free_ranges = block.free_ranges().collect::<Vec<_>>();
+        assert_eq!(free_ranges.len(), 1);
+        assert_eq!(free_ranges[0].offset, Some(1114112));
+        assert!(stats.default_pool(1).unwrap().blocks.is_empty());
+
+        let pools = stats.custom_pools(1);
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].index, 0);
+        assert_eq!(pools[0].name.as_deref(), Some("staging"));
+        assert_eq!(pools[0].blocks["1"].allocations().count(), 1);
+        assert_eq!(pools[1].index, 1);
+        assert_eq!(pools[1].name, None);
+        assert_eq!(pools[1].dedicated_allocations[0].offset, None);
+        assert_eq!(pools[1].dedicated_allocations[0].size, 196608);
+        assert!(stats.custom_pools(0).is_empty());
+    }
+
+    #[test]
+    fn custom_pool_names_round_trip() {
+        let stats: Stats = serde_json::from_str(DETAILED_DUMP).unwrap();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains(r#""Name":"0 - staging""#));
+        assert!(json.contains(r#""Name":"1""#));
+        assert_eq!(serde_json::from_str::<Stats>(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn parses_heap_statistics() {
+        let stats: Stats = serde_json::from_str(DETAILED_DUMP).unwrap();
+
+        assert_eq!(stats.total.block_count, 3);
+        assert_eq!(stats.total.unused_range_size_min, None);
+        let heap = stats.heap(1).unwrap();
+        assert_eq!(heap.budget.usage_bytes, heap.stats.block_bytes);
+        assert_eq!(heap.stats.allocation_size_min, Some(65536));
+        assert_eq!(heap.stats.allocation_size_max, Some(196608));
+        assert_eq!(heap.memory_types()[0].1.stats, heap.stats);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn parses_dump_of_fake_device() {
+        use ash::vk;
+
+        use crate::testing::{FakeDevice, MemoryTopology};
+        use crate::{vma, AllocatedBuffer, AllocationOptions};
+
+        let device = FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .heap(64 << 20, vk::MemoryHeapFlags::empty())
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL)
+                .memory_type(
+                    1,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                ),
+        );
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let buffer_info = |size| {
+            vk::BufferCreateInfo::default()
+                .size(size)
+                .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+        };
+
+        let options =
+            AllocationOptions::new().required_flags(vk::MemoryPropertyFlags::DEVICE_LOCAL);
+        let device_buffer =
+            AllocatedBuffer::create(&allocator, &buffer_info(1024), &options).unwrap();
+
+        let pool_info = vma::PoolCreateInfo::default()
+            .memory_type_index(1)
+            .block_size(1 << 20);
+        let pool = unsafe { allocator.create_pool(&pool_info) }.unwrap();
+        unsafe { allocator.set_pool_name(pool, Some(c"staging")) };
+        let pool_info = vma::AllocationCreateInfo::default().pool(pool);
+        let pool_buffer =
+            unsafe { AllocatedBuffer::new(&allocator, &buffer_info(4096), &pool_info) }.unwrap();
+        let dedicated_info = pool_info.flags(vma::AllocationCreateFlags::DEDICATED_MEMORY);
+        let dedicated_buffer =
+            unsafe { AllocatedBuffer::new(&allocator, &buffer_info(8192), &dedicated_info) }
+                .unwrap();
+
+        let stats = allocator.parse_stats(true).unwrap();
+
+        assert_eq!(stats.general.gpu, "ash-mem-alloc fake device");
+        assert_eq!(
+            stats.general.device_type,
+            vk::PhysicalDeviceType::CPU.as_raw()
+        );
+        assert_eq!(stats.general.memory_type_count, 2);
+        assert_eq!(stats.heaps().len(), 2);
+        assert_eq!(stats.heap(1).unwrap().size, 64 << 20);
+        assert_eq!(stats.memory_type(1).unwrap().stats.allocation_count, 2);
+
+        assert_eq!(stats.total.allocation_count, 3);
+        assert_eq!(stats.total.allocation_bytes, 1024 + 4096 + 8192);
+        assert_eq!(stats.total.allocation_size_min, Some(1024));
+        assert_eq!(stats.total.allocation_size_max, Some(8192));
+
+        let default_pool = stats.default_pool(0).unwrap();
+        assert_eq!(default_pool.blocks.len(), 1);
+        let allocations = default_pool
+            .blocks
+            .values()
+            .flat_map(Block::allocations)
+            .collect::<Vec<_>>();
+        assert_eq!(allocations.len(), 1);
+        assert_eq!(allocations[0].kind, "BUFFER");
+        assert_eq!(allocations[0].size, 1024);
+        assert!(default_pool.dedicated_allocations.is_empty());
+        assert!(stats.default_pool(1).unwrap().blocks.is_empty());
+
+        let pools = stats.custom_pools(1);
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].index, 0);
+        assert_eq!(pools[0].name.as_deref(), Some("staging"));
+        assert_eq!(pools[0].blocks.len(), 1);
+        assert_eq!(pools[0].dedicated_allocations.len(), 1);
+        assert_eq!(pools[0].dedicated_allocations[0].offset, None);
+        assert_eq!(pools[0].dedicated_allocations[0].size, 8192);
+        assert!(stats.custom_pools(0).is_empty());
+
+        drop((device_buffer, pool_buffer, dedicated_buffer));
+        unsafe { allocator.destroy_pool(pool) };
+    }
+}
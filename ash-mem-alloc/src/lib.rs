@@ -13,21 +13,109 @@
 //!     .vulkan_functions(&functions);
 //! ```
 //!
+//! [`vma::VulkanFunctions::from_loaders`] fills the function table from the `ash` loaders with the functions of the API version,
+//! [`vma::VulkanFunctions::from_ash`] additionally falls back to the `KHR` functions of the enabled extensions.
+//! [`vma::AllocatorCreateFlags::detect`] drops the allocator flags whose device extension or feature was not enabled.
+//!
 //! While certain convenience features are implemented, all functions are unsafe and operate on raw `Vma` and `Vk` handles, just like in `ash`.
 //! For the most part, functions will generally behave as one would expect from identical `ash` functions.
+//!
+//! For code that does not want to deal with raw handles, [`Allocator`] provides a safe, reference-counted
+//! wrapper that destroys the underlying VMA allocator once it is no longer used.
+//! [`AllocatedBuffer`] and [`AllocatedImage`] tie a resource to its allocation and free both on drop,
+//! [`AllocationOptions`] describes allocations that can be created without `unsafe`,
+//! [`MappedAllocation`] keeps an allocation mapped and takes care of flushing and invalidating.
+//! [`Defragmenter`] drives the incremental defragmentation protocol and only asks what to do with each move.
+//! [`BudgetMonitor`] samples the heap budgets every frame and reports when a heap crosses a usage threshold.
+//! [`AllocationPolicy`] falls back through a list of allocation candidates, e.g. from device-local to host memory.
+//!
+//! ## Features
+//! - `bundled` (default): compile the vendored VMA sources, which requires a C++ compiler and the git submodules
+//! - `bytemuck`: bounds-checked `write_slice`/`read_vec` helpers for `bytemuck::Pod` data
+//! - `testing`: the `testing` module, a software Vulkan device for running allocators without a GPU
+//! - `debug-detect-corruption`, `debug-initialize-allocations`, `debug-always-dedicated-memory`, `debug-global-mutex`:
+//!   compile VMA with the corresponding `VMA_DEBUG_*` macro enabled
+//! - `assert-hook`: report failed `VMA_ASSERT`s through `set_assert_handler`, panicking by default
+//! - `heavy-assert`: like `assert-hook`, but also checks the expensive `VMA_HEAVY_ASSERT`s
+//! - `static-vulkan-functions`: let VMA call the Vulkan functions of the system loader directly instead of looking
+//!   them up through [`vma::VulkanFunctions`], linking against `vulkan`/`vulkan-1`
+//! - `log`, `tracing`: emit the internal debug messages of VMA at debug level under the `vma` target,
+//!   e.g. block allocations and defragmentation decisions. `tracing` takes precedence if both are enabled.
+//! - `serde`: `Serialize`/`Deserialize` for plain-data structs like [`vma::Budget`] and for all enums and flags,
+//!   which are written by name in human-readable formats and as raw bits otherwise
+//! - `json`: the `json` module, a typed model of the JSON statistics dump returned by `vma::parse_stats`.
+//!   Enables `serde` and pulls in `serde_json`.
+//!
+//! ## VMA debug configuration
+//! The VMA debug macros can also be set through environment variables of the same name when building this crate:
+//! - `VMA_DEBUG_DETECT_CORRUPTION`, `VMA_DEBUG_INITIALIZE_ALLOCATIONS`, `VMA_DEBUG_ALWAYS_DEDICATED_MEMORY`
+//!   and `VMA_DEBUG_GLOBAL_MUTEX` enable the respective switch if set to a non-zero value
+//! - `VMA_DEBUG_MARGIN` sets the margin in bytes around every allocation
+//! - `VMA_DEBUG_MIN_BUFFER_IMAGE_GRANULARITY` overrides the minimum buffer-image granularity
+//!
+//! Corruption detection requires a margin, which defaults to 16 bytes if `VMA_DEBUG_MARGIN` is not set.
+//! Without it, [`Allocator::check_corruption`] returns `VK_ERROR_FEATURE_NOT_PRESENT`.
+//!
+//! ## Linking a prebuilt VMA
+//! Instead of compiling the vendored sources, an existing `vma` library can be linked:
+//! - if `VMA_LIB_DIR` is set, the library is linked from that directory, statically if `VMA_STATIC` is set to a non-zero value
+//! - otherwise, if the `bundled` feature is disabled or the submodules are not checked out, `vma` is looked up through pkg-config
+//!
+//! The library must export the VMA C API. The build options above only apply to the bundled sources.
 
 mod enums;
 mod structs;
 mod function_ptrs;
 mod handles;
 mod functions;
+mod error;
+mod stats;
+mod pass;
+mod vulkan_functions;
+mod allocator_flags;
+mod names;
 
 mod ffi;
 
+mod allocator;
+mod budget_monitor;
+mod defragmenter;
+mod mapping;
+mod options;
+mod policy;
+mod resources;
+#[cfg(feature = "bytemuck")]
+mod transfer;
+#[cfg(feature = "assert-hook")]
+mod assert;
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod debug_log;
+
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use allocator::Allocator;
+pub use budget_monitor::{BudgetMonitor, Crossing, HeapSample, ThresholdEvent};
+pub use defragmenter::{Defragmenter, MoveAction};
+pub use mapping::MappedAllocation;
+pub use options::AllocationOptions;
+pub use policy::{AllocationPolicy, Tiered};
+pub use resources::{AllocatedBuffer, AllocatedImage};
+#[cfg(feature = "bytemuck")]
+pub use transfer::TransferError;
+#[cfg(feature = "assert-hook")]
+pub use assert::{assert_handler, set_assert_handler, AssertHandler};
+
 pub mod vma {
     pub use super::enums::*;
     pub use super::structs::*;
     pub use super::function_ptrs::*;
     pub use super::handles::*;
     pub use super::functions::*;
+    pub use super::error::*;
+    pub use super::stats::*;
+    pub use super::pass::*;
+    pub use super::allocator_flags::*;
 }
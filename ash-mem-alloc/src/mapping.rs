@@ -0,0 +1,307 @@
+use std::ops::Range;
+
+use ash::vk;
+
+use crate::{vma, AllocatedBuffer, AllocatedImage, Allocator};
+
+/// Scoped host mapping of an allocation.
+///
+/// Dereferences to the full allocation as a byte slice.
+/// For memory types that are not `HOST_COHERENT`, the allocation is invalidated when it is mapped,
+/// and the written range is flushed when it is unmapped.
+/// Writes through [`std::ops::DerefMut`] mark the whole allocation as written,
+/// [`MappedAllocation::range_mut`] only marks the given range.
+///
+/// The mapping is released on drop. Use [`MappedAllocation::unmap`] to observe flush errors.
+#[derive(Debug)]
+pub struct MappedAllocation<'a> {
+    allocator: &'a Allocator,
+    allocation: vma::Allocation,
+    data: *mut u8,
+    size: usize,
+    coherent: bool,
+    /// Range written since the last flush
+    dirty: Option<Range<usize>>,
+}
+
+impl<'a> MappedAllocation<'a> {
+    /// Maps `allocation` into host memory.
+    ///
+    /// See [`vma::map_memory`]
+    ///
+    /// # Safety
+    /// - `allocation` must be a live allocation created by `allocator` in host visible memory
+    ///   and must outlive the returned mapping.
+    /// - The device must not access the allocation while the mapping is alive.
+    /// - There must not be any other [`MappedAllocation`] for `allocation` at the same time.
+    pub unsafe fn new(
+        allocator: &'a Allocator,
+        allocation: vma::Allocation,
+    ) -> Result<Self, vma::Error> {
+        let size = allocator.get_allocation_info(allocation).size as usize;
+        let coherent = allocator
+            .get_allocation_memory_properties(allocation)
+            .contains(vk::MemoryPropertyFlags::HOST_COHERENT);
+
+        let data = allocator.map_memory(allocation)?.cast::<u8>();
+
+        let mapping = Self {
+            allocator,
+            allocation,
+            data,
+            size,
+            coherent,
+            dirty: None,
+        };
+
+        // make device writes visible to the host before anything is read
+        mapping.invalidate()?;
+
+        Ok(mapping)
+    }
+
+    /// Maps `allocation` into host memory after checking that it lives in a `HOST_VISIBLE` memory type.
+    ///
+    /// Fails with `VK_ERROR_MEMORY_MAP_FAILED` if it does not.
+    ///
+    /// # Safety
+    /// Same as [`MappedAllocation::new`], except that the allocation does not need to be host visible.
+    unsafe fn new_checked(
+        allocator: &'a Allocator,
+        allocation: vma::Allocation,
+    ) -> Result<Self, vma::Error> {
+        if !allocator
+            .get_allocation_memory_properties(allocation)
+            .contains(vk::MemoryPropertyFlags::HOST_VISIBLE)
+        {
+            return Err(vma::Error::new(
+                "vmaMapMemory",
+                vk::Result::ERROR_MEMORY_MAP_FAILED,
+            ));
+        }
+        Self::new(allocator, allocation)
+    }
+
+    /// Returns the raw allocation handle
+    pub fn allocation(&self) -> vma::Allocation {
+        self.allocation
+    }
+
+    /// Returns whether the mapped memory type is `HOST_COHERENT`.
+    ///
+    /// Coherent memory never needs to be flushed or invalidated.
+    pub fn is_coherent(&self) -> bool {
+        self.coherent
+    }
+
+    /// Invalidates the whole allocation, making device writes visible to the host.
+    ///
+    /// This is a no-op for coherent memory.
+    pub fn invalidate(&self) -> Result<(), vma::Error> {
+        if self.coherent {
+            return Ok(());
+        }
+        unsafe {
+            self.allocator
+                .invalidate_allocation(self.allocation, 0, vk::WHOLE_SIZE)
+        }
+    }
+
+    /// Returns the bytes in `range` for writing, marking only that range as written.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, like indexing a slice.
+    pub fn range_mut(&mut self, range: Range<usize>) -> &mut [u8] {
+        let bytes = unsafe { std::slice::from_raw_parts_mut(self.data, self.size) };
+        let bytes = &mut bytes[range.clone()];
+        self.mark_written(range);
+        bytes
+    }
+
+    /// Returns the range written since the last flush, if any
+    pub fn written_range(&self) -> Option<Range<usize>> {
+        self.dirty.clone()
+    }
+
+    /// Extends the written range to cover `range`
+    fn mark_written(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        self.dirty = Some(match self.dirty.take() {
+            Some(dirty) => dirty.start.min(range.start)..dirty.end.max(range.end),
+            None => range,
+        });
+    }
+
+    /// Flushes the range written since the last flush, making host writes visible to the device.
+    ///
+    /// The range is rounded to `nonCoherentAtomSize` by VMA.
+    /// This is a no-op for coherent memory or if nothing has been written.
+    pub fn flush(&mut self) -> Result<(), vma::Error> {
+        let Some(dirty) = self.dirty.clone() else {
+            return Ok(());
+        };
+        if !self.coherent {
+            unsafe {
+                self.allocator.flush_allocation(
+                    self.allocation,
+                    dirty.start as vk::DeviceSize,
+                    dirty.len() as vk::DeviceSize,
+                )?
+            };
+        }
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Flushes pending writes and unmaps the allocation.
+    ///
+    /// The allocation is unmapped even if flushing fails.
+    pub fn unmap(mut self) -> Result<(), vma::Error> {
+        let result = self.flush();
+        // don't retry a failed flush on drop
+        self.dirty = None;
+        result
+    }
+}
+
+impl std::ops::Deref for MappedAllocation<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.data, self.size) }
+    }
+}
+
+impl std::ops::DerefMut for MappedAllocation<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.range_mut(0..self.size)
+    }
+}
+
+impl Drop for MappedAllocation<'_> {
+    fn drop(&mut self) {
+        // errors can not be reported from drop, use `unmap` to observe them
+        let _ = self.flush();
+        unsafe { self.allocator.unmap_memory(self.allocation) };
+    }
+}
+
+impl AllocatedBuffer {
+    /// Maps the memory of this buffer into host memory.
+    ///
+    /// The device must not access the buffer while the mapping is alive.
+    /// Fails with `VK_ERROR_MEMORY_MAP_FAILED` if the memory is not `HOST_VISIBLE`.
+    ///
+    /// See [`MappedAllocation`]
+    pub fn map(&mut self) -> Result<MappedAllocation<'_>, vma::Error> {
+        let allocation = self.allocation();
+        unsafe { MappedAllocation::new_checked(self.allocator(), allocation) }
+    }
+}
+
+impl AllocatedImage {
+    /// Maps the memory of this image into host memory.
+    ///
+    /// The device must not access the image while the mapping is alive.
+    /// Fails with `VK_ERROR_MEMORY_MAP_FAILED` if the memory is not `HOST_VISIBLE`.
+    ///
+    /// See [`MappedAllocation`]
+    pub fn map(&mut self) -> Result<MappedAllocation<'_>, vma::Error> {
+        let allocation = self.allocation();
+        unsafe { MappedAllocation::new_checked(self.allocator(), allocation) }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use ash::vk;
+
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::{vma, AllocatedBuffer, AllocationOptions};
+
+    /// `nonCoherentAtomSize` of fake devices
+    const ATOM_SIZE: vk::DeviceSize = 64;
+
+    /// A device whose only memory type is host visible, but not coherent
+    fn device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::empty())
+                .memory_type(0, vk::MemoryPropertyFlags::HOST_VISIBLE),
+        )
+    }
+
+    fn buffer(allocator: &crate::Allocator) -> AllocatedBuffer {
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(1024)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC);
+        let options = AllocationOptions::new()
+            .usage(vma::MemoryUsage::AUTO)
+            .flags(vma::AllocationCreateFlags::HOST_ACCESS_RANDOM);
+        AllocatedBuffer::create(allocator, &buffer_info, &options).unwrap()
+    }
+
+    #[test]
+    fn mapping_invalidates_the_allocation() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator);
+        let (memory, offset, size) = (buffer.device_memory(), buffer.offset(), buffer.size());
+
+        let mapping = buffer.map().unwrap();
+        assert!(!mapping.is_coherent());
+        assert_eq!(
+            device.take_invalidated_ranges(),
+            [(memory, offset..offset + size)]
+        );
+        drop(mapping);
+        assert!(device.take_invalidated_ranges().is_empty());
+    }
+
+    #[test]
+    fn reads_do_not_flush() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator);
+
+        let mapping = buffer.map().unwrap();
+        assert!(mapping.iter().all(|&byte| byte == 0));
+        assert_eq!(mapping.written_range(), None);
+        mapping.unmap().unwrap();
+
+        assert!(device.take_flushed_ranges().is_empty());
+    }
+
+    #[test]
+    fn range_mut_flushes_the_rounded_written_range() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator);
+        let (memory, offset) = (buffer.device_memory(), buffer.offset());
+
+        let mut mapping = buffer.map().unwrap();
+        mapping.range_mut(100..130).fill(0xab);
+        mapping.range_mut(140..150).fill(0xcd);
+        assert_eq!(mapping.written_range(), Some(100..150));
+        mapping.unmap().unwrap();
+
+        // 100..150 grows to the enclosing atoms, 64..192
+        let start = offset + 100 / ATOM_SIZE * ATOM_SIZE;
+        let end = offset + 150_u64.next_multiple_of(ATOM_SIZE);
+        assert_eq!(device.take_flushed_ranges(), [(memory, start..end)]);
+
+        let mapping = buffer.map().unwrap();
+        assert!(mapping[100..130].iter().all(|&byte| byte == 0xab));
+        assert_eq!(mapping[130], 0);
+        drop(mapping);
+        assert!(device.take_flushed_ranges().is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+//! Formatting and optional name-based (de)serialization of the generated enum and flag types.
+//!
+//! In human-readable formats like JSON, enums are written as the name of their variant, e.g. `"AUTO"`,
+//! and flags as a list of the names of their set bits, e.g. `["MAPPED", "WITHIN_BUDGET"]`.
+//! Enum values without a name are written as a decimal string and flag bits without a name
+//! as a single hex string at the end of the list, e.g. `"0x80000000"`, so nothing is lost.
+//! Other formats like bincode get the raw `u32`.
+//! Neither representation relies on `Deserializer::deserialize_any`, so formats that are not self-describing work.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Splits `flags` into the names of its bits and the remaining bits that have no name.
+///
+/// Names are taken in declaration order, so aliases and masks of already named bits are left out.
+fn decompose(flags: u32, names: &[(&'static str, u32)]) -> (Vec<&'static str>, u32) {
+    let mut remaining = flags;
+    let mut list = Vec::new();
+    for &(name, bits) in names {
+        if bits != 0 && remaining & bits == bits {
+            list.push(name);
+            remaining &= !bits;
+        }
+    }
+    (list, remaining)
+}
+
+/// Formats `value` as the name of the first variant with that value, or as a number if there is none
+pub(crate) fn fmt_value(
+    f: &mut fmt::Formatter<'_>,
+    value: u32,
+    names: &[(&'static str, u32)],
+) -> fmt::Result {
+    match names.iter().find(|&&(_, candidate)| candidate == value) {
+        Some(&(name, _)) => f.write_str(name),
+        None => write!(f, "{value}"),
+    }
+}
+
+/// Formats `flags` as the names of its bits joined by `" | "`, followed by the remaining bits as hex.
+///
+/// Empty flags are formatted as `(empty)`.
+pub(crate) fn fmt_flags(
+    f: &mut fmt::Formatter<'_>,
+    flags: u32,
+    names: &[(&'static str, u32)],
+) -> fmt::Result {
+    if flags == 0 {
+        return f.write_str("(empty)");
+    }
+
+    let (list, remaining) = decompose(flags, names);
+    let mut separator = "";
+    for name in list {
+        write!(f, "{separator}{name}")?;
+        separator = " | ";
+    }
+    if remaining != 0 {
+        write!(f, "{separator}{remaining:#x}")?;
+    }
+    Ok(())
+}
+
+/// Looks up the value of `name` in `names`, falling back to `parse` for values without a name
+#[cfg(feature = "serde")]
+fn value_of<E: serde::de::Error>(
+    type_name: &str,
+    names: &[(&str, u32)],
+    name: &str,
+    parse: impl FnOnce(&str) -> Option<u32>,
+) -> Result<u32, E> {
+    names
+        .iter()
+        .find(|&&(candidate, _)| candidate == name)
+        .map(|&(_, value)| value)
+        .or_else(|| parse(name))
+        .ok_or_else(|| E::custom(format!("unknown {type_name} `{name}`")))
+}
+
+/// Writes `value` as the name of the first variant with that value, or as a decimal string if there is none.
+///
+/// Formats that are not human-readable get the raw value.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_value<S: Serializer>(
+    value: u32,
+    names: &[(&'static str, u32)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if !serializer.is_human_readable() {
+        return serializer.serialize_u32(value);
+    }
+    match names.iter().find(|&&(_, candidate)| candidate == value) {
+        Some(&(name, _)) => serializer.serialize_str(name),
+        None => serializer.collect_str(&value),
+    }
+}
+
+/// Reads a value written by [`serialize_value`]
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_value<'de, D: Deserializer<'de>>(
+    type_name: &str,
+    names: &[(&'static str, u32)],
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    if !deserializer.is_human_readable() {
+        return u32::deserialize(deserializer);
+    }
+    let name = String::deserialize(deserializer)?;
+    value_of(type_name, names, &name, |name| name.parse().ok())
+}
+
+/// Writes `flags` as a list of names, remaining bits without a name are written as a single hex string at the end.
+///
+/// Formats that are not human-readable get the raw bits.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_flags<S: Serializer>(
+    flags: u32,
+    names: &[(&'static str, u32)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if !serializer.is_human_readable() {
+        return serializer.serialize_u32(flags);
+    }
+    let (list, remaining) = decompose(flags, names);
+    let mut list = list.into_iter().map(str::to_owned).collect::<Vec<_>>();
+    if remaining != 0 {
+        list.push(format!("{remaining:#x}"));
+    }
+    serializer.collect_seq(list)
+}
+
+/// Reads flags written by [`serialize_flags`]
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_flags<'de, D: Deserializer<'de>>(
+    type_name: &str,
+    names: &[(&'static str, u32)],
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    if !deserializer.is_human_readable() {
+        return u32::deserialize(deserializer);
+    }
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .try_fold(0, |flags, name| {
+            let bits = value_of::<D::Error>(type_name, names, name, |name| {
+                u32::from_str_radix(name.strip_prefix("0x")?, 16).ok()
+            })?;
+            Ok(flags | bits)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vma::{AllocationCreateFlags, DefragmentationMoveOperation, MemoryUsage};
+
+    /// Formats `value` with both `Debug` and `Display` and checks that they agree
+    fn formatted(value: impl std::fmt::Debug + std::fmt::Display) -> String {
+        let debug = format!("{value:?}");
+        assert_eq!(debug, value.to_string());
+        debug
+    }
+
+    #[test]
+    fn aliases_are_printed_once() {
+        assert_eq!(
+            formatted(AllocationCreateFlags::STRATEGY_BEST_FIT),
+            "STRATEGY_MIN_MEMORY"
+        );
+        assert_eq!(
+            formatted(AllocationCreateFlags::STRATEGY_MIN_MEMORY),
+            "STRATEGY_MIN_MEMORY"
+        );
+        // masks are made up of bits that were already named
+        assert_eq!(
+            formatted(AllocationCreateFlags::STRATEGY_MASK),
+            "STRATEGY_MIN_MEMORY | STRATEGY_MIN_TIME | STRATEGY_MIN_OFFSET"
+        );
+    }
+
+    #[test]
+    fn unknown_bits_are_printed_as_hex() {
+        assert_eq!(
+            formatted(AllocationCreateFlags::MAPPED | AllocationCreateFlags::from_raw(0x8000_0000)),
+            "MAPPED | 0x80000000"
+        );
+        assert_eq!(
+            formatted(AllocationCreateFlags::from_raw(0x8000_0000)),
+            "0x80000000"
+        );
+    }
+
+    #[test]
+    fn empty_flags() {
+        assert_eq!(formatted(AllocationCreateFlags::empty()), "(empty)");
+    }
+
+    #[test]
+    fn enums_are_printed_by_name() {
+        assert_eq!(formatted(MemoryUsage::GPU_ONLY), "GPU_ONLY");
+        assert_eq!(formatted(DefragmentationMoveOperation::IGNORE), "IGNORE");
+        assert_eq!(formatted(MemoryUsage::from_raw(42)), "42");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn human_readable_formats_use_names() {
+        let flags = AllocationCreateFlags::MAPPED
+            | AllocationCreateFlags::WITHIN_BUDGET
+            | AllocationCreateFlags::from_raw(0x8000_0000);
+        let json = serde_json::to_string(&flags).unwrap();
+        assert_eq!(json, r#"["MAPPED","WITHIN_BUDGET","0x80000000"]"#);
+        assert_eq!(
+            serde_json::from_str::<AllocationCreateFlags>(&json).unwrap(),
+            flags
+        );
+
+        assert_eq!(
+            serde_json::to_string(&MemoryUsage::AUTO).unwrap(),
+            r#""AUTO""#
+        );
+        let unnamed = MemoryUsage::from_raw(42);
+        let json = serde_json::to_string(&unnamed).unwrap();
+        assert_eq!(json, r#""42""#);
+        assert_eq!(serde_json::from_str::<MemoryUsage>(&json).unwrap(), unnamed);
+
+        assert!(serde_json::from_str::<MemoryUsage>(r#""NOT_A_USAGE""#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn other_formats_use_raw_bits() {
+        let flags = AllocationCreateFlags::MAPPED | AllocationCreateFlags::from_raw(0x8000_0000);
+        let bytes = bincode::serialize(&flags).unwrap();
+        assert_eq!(bytes, flags.into_raw().to_le_bytes());
+        assert_eq!(
+            bincode::deserialize::<AllocationCreateFlags>(&bytes).unwrap(),
+            flags
+        );
+
+        let bytes = bincode::serialize(&MemoryUsage::GPU_ONLY).unwrap();
+        assert_eq!(
+            bincode::deserialize::<MemoryUsage>(&bytes).unwrap(),
+            MemoryUsage::GPU_ONLY
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use ash::vk;
+
+use crate::vma;
+
+/// A [`vma::AllocationCreateInfo`] without a pool or user data.
+///
+/// Everything else is plain data, so the safe methods of [`Allocator`](crate::Allocator),
+/// [`AllocatedBuffer`](crate::AllocatedBuffer) and [`AllocatedImage`](crate::AllocatedImage) taking
+/// `AllocationOptions` can pass it to VMA without any unchecked raw pointers.
+/// Allocations from custom pools or with user data still go through the `unsafe` methods taking a
+/// [`vma::AllocationCreateInfo`].
+///
+/// ```rust ignore
+/// let options = AllocationOptions::new()
+///     .usage(vma::MemoryUsage::AUTO)
+///     .flags(vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE);
+/// let buffer = AllocatedBuffer::create(&allocator, &buffer_info, &options)?;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllocationOptions {
+    info: vma::AllocationCreateInfo,
+}
+
+impl AllocationOptions {
+    /// Creates options with [`vma::MemoryUsage::UNKNOWN`] and no flags
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`vma::AllocationCreateInfo::flags`]
+    pub fn flags(mut self, flags: vma::AllocationCreateFlags) -> Self {
+        self.info.flags = flags;
+        self
+    }
+
+    /// See [`vma::AllocationCreateInfo::usage`]
+    pub fn usage(mut self, usage: vma::MemoryUsage) -> Self {
+        self.info.usage = usage;
+        self
+    }
+
+    /// See [`vma::AllocationCreateInfo::required_flags`]
+    pub fn required_flags(mut self, required_flags: vk::MemoryPropertyFlags) -> Self {
+        self.info.required_flags = required_flags;
+        self
+    }
+
+    /// See [`vma::AllocationCreateInfo::preferred_flags`]
+    pub fn preferred_flags(mut self, preferred_flags: vk::MemoryPropertyFlags) -> Self {
+        self.info.preferred_flags = preferred_flags;
+        self
+    }
+
+    /// See [`vma::AllocationCreateInfo::memory_type_bits`]
+    pub fn memory_type_bits(mut self, memory_type_bits: u32) -> Self {
+        self.info.memory_type_bits = memory_type_bits;
+        self
+    }
+
+    /// See [`vma::AllocationCreateInfo::priority`]
+    pub fn priority(mut self, priority: f32) -> Self {
+        self.info.priority = priority;
+        self
+    }
+
+    /// Returns the create info passed to VMA
+    pub fn create_info(&self) -> &vma::AllocationCreateInfo {
+        &self.info
+    }
+}
+
+impl From<AllocationOptions> for vma::AllocationCreateInfo {
+    fn from(options: AllocationOptions) -> Self {
+        options.info
+    }
+}
+
+/// Panics if `buffer_create_info` carries raw pointers that VMA would read,
+/// i.e. a `p_next` chain or queue family indices for concurrent sharing
+pub(crate) fn assert_plain_buffer_info(buffer_create_info: &vk::BufferCreateInfo) {
+    assert!(
+        buffer_create_info.p_next.is_null()
+            && buffer_create_info.sharing_mode == vk::SharingMode::EXCLUSIVE,
+        "buffer create infos with a `p_next` chain or concurrent sharing need the `unsafe` functions"
+    );
+}
+
+/// Panics if `image_create_info` carries raw pointers that VMA would read,
+/// i.e. a `p_next` chain or queue family indices for concurrent sharing
+pub(crate) fn assert_plain_image_info(image_create_info: &vk::ImageCreateInfo) {
+    assert!(
+        image_create_info.p_next.is_null()
+            && image_create_info.sharing_mode == vk::SharingMode::EXCLUSIVE,
+        "image create infos with a `p_next` chain or concurrent sharing need the `unsafe` functions"
+    );
+}
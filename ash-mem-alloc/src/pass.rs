@@ -0,0 +1,39 @@
+use crate::vma;
+
+/// Outcome of [`vma::begin_defragmentation_pass`]
+#[derive(Debug, Clone, Copy)]
+pub enum PassResult<'a> {
+    /// No more moves are possible, the pass does not need to be ended
+    /// and defragmentation can be finished with [`vma::end_defragmentation`]
+    Done,
+    /// The moves in the pass have to be performed or have their operation changed
+    /// before the pass is ended with [`vma::end_defragmentation_pass`]
+    Moves(vma::DefragmentationPassMoveInfo<'a>),
+}
+
+impl<'a> PassResult<'a> {
+    /// Returns the moves of the pass, or `None` if defragmentation is done
+    pub fn moves(self) -> Option<vma::DefragmentationPassMoveInfo<'a>> {
+        match self {
+            Self::Done => None,
+            Self::Moves(pass_info) => Some(pass_info),
+        }
+    }
+
+    /// Checks whether no more moves are possible
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Done)
+    }
+}
+
+/// Built from the status of [`vma::begin_defragmentation_pass`],
+/// where `true` means `VK_INCOMPLETE` and therefore moves to perform.
+impl<'a> From<(bool, vma::DefragmentationPassMoveInfo<'a>)> for PassResult<'a> {
+    fn from((incomplete, pass_info): (bool, vma::DefragmentationPassMoveInfo<'a>)) -> Self {
+        if incomplete {
+            Self::Moves(pass_info)
+        } else {
+            Self::Done
+        }
+    }
+}
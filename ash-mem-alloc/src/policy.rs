@@ -0,0 +1,264 @@
+use ash::vk;
+
+use crate::{vma, AllocatedBuffer, AllocatedImage, Allocator};
+
+/// A resource created by an [`AllocationPolicy`] together with the tier it was created with
+#[derive(Debug)]
+pub struct Tiered<T> {
+    /// Index of the [`vma::AllocationCreateInfo`] in [`AllocationPolicy::tiers`] that was used
+    pub tier: usize,
+    /// The created resource
+    pub resource: T,
+}
+
+/// An ordered list of [`vma::AllocationCreateInfo`]s that are tried one after another
+/// until a resource can be created, e.g. device-local memory within the budget first
+/// and host-visible memory as a fallback.
+///
+/// For every tier, the memory type is looked up with [`Allocator::find_memory_type_index_for_buffer_info`]
+/// or [`Allocator::find_memory_type_index_for_image_info`] and tiers without a matching memory type are skipped.
+/// Tiers with [`vma::AllocationCreateFlags::WITHIN_BUDGET`] are also skipped without trying to allocate
+/// if [`Allocator::get_heap_budgets`] shows that the heap of that memory type has no room left.
+/// If creating the resource fails because memory ran out, the next tier is tried.
+///
+/// ```rust ignore
+/// let policy = AllocationPolicy::new()
+///     .tier(
+///         vma::AllocationCreateInfo::default()
+///             .usage(vma::MemoryUsage::AUTO_PREFER_DEVICE)
+///             .flags(vma::AllocationCreateFlags::WITHIN_BUDGET),
+///     )
+///     .tier(
+///         vma::AllocationCreateInfo::default()
+///             .usage(vma::MemoryUsage::AUTO_PREFER_HOST)
+///             .flags(vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE),
+///     );
+///
+/// let texture = unsafe { policy.create_image(&allocator, &image_info)? };
+/// if texture.tier > 0 {
+///     // the texture ended up in host memory, lower the streaming quality
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AllocationPolicy {
+    tiers: Vec<vma::AllocationCreateInfo>,
+}
+
+impl AllocationPolicy {
+    /// Creates a policy without any tiers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tier that is tried after all tiers added before it
+    pub fn tier(mut self, allocation_create_info: vma::AllocationCreateInfo) -> Self {
+        self.tiers.push(allocation_create_info);
+        self
+    }
+
+    /// Returns all tiers in the order they are tried
+    pub fn tiers(&self) -> &[vma::AllocationCreateInfo] {
+        &self.tiers
+    }
+
+    /// Creates a buffer with the first tier that has a fitting memory type and enough memory left.
+    ///
+    /// Returns the error of the last tier if none of them succeeds.
+    ///
+    /// # Safety
+    /// `buffer_create_info` and every tier must fulfill the requirements of [`Allocator::create_buffer`].
+    pub unsafe fn create_buffer(
+        &self,
+        allocator: &Allocator,
+        buffer_create_info: &vk::BufferCreateInfo,
+    ) -> Result<Tiered<AllocatedBuffer>, vma::Error> {
+        self.try_tiers(
+            allocator,
+            "vmaCreateBuffer",
+            buffer_create_info.size,
+            |info| allocator.find_memory_type_index_for_buffer_info(buffer_create_info, info),
+            |info| AllocatedBuffer::new(allocator, buffer_create_info, info),
+        )
+    }
+
+    /// Creates an image with the first tier that has a fitting memory type and enough memory left.
+    ///
+    /// The size of an image is not known before it is created, so the budget check only skips
+    /// heaps that are already over budget.
+    ///
+    /// Returns the error of the last tier if none of them succeeds.
+    ///
+    /// # Safety
+    /// `image_create_info` and every tier must fulfill the requirements of [`Allocator::create_image`].
+    pub unsafe fn create_image(
+        &self,
+        allocator: &Allocator,
+        image_create_info: &vk::ImageCreateInfo,
+    ) -> Result<Tiered<AllocatedImage>, vma::Error> {
+        self.try_tiers(
+            allocator,
+            "vmaCreateImage",
+            0,
+            |info| allocator.find_memory_type_index_for_image_info(image_create_info, info),
+            |info| AllocatedImage::new(allocator, image_create_info, info),
+        )
+    }
+
+    /// Tries all tiers in order, `function` names the VMA function reported if no tier could be tried at all
+    fn try_tiers<T>(
+        &self,
+        allocator: &Allocator,
+        function: &'static str,
+        size: vk::DeviceSize,
+        find_memory_type_index: impl Fn(&vma::AllocationCreateInfo) -> Result<u32, vma::Error>,
+        mut create: impl FnMut(&vma::AllocationCreateInfo) -> Result<T, vma::Error>,
+    ) -> Result<Tiered<T>, vma::Error> {
+        let memory_types = allocator.get_memory_properties().memory_types;
+        let budgets = allocator.get_heap_budgets();
+
+        let mut last_error = None;
+        for (tier, info) in self.tiers.iter().enumerate() {
+            let memory_type_index = match find_memory_type_index(info) {
+                Ok(memory_type_index) => memory_type_index,
+                Err(error) => {
+                    last_error = Some(error);
+                    continue;
+                }
+            };
+
+            if info
+                .flags
+                .contains(vma::AllocationCreateFlags::WITHIN_BUDGET)
+            {
+                let heap_index = memory_types[memory_type_index as usize].heap_index;
+                let budget = &budgets[heap_index as usize];
+                if budget.usage.saturating_add(size) > budget.budget {
+                    last_error = Some(
+                        vma::Error::new(function, vk::Result::ERROR_OUT_OF_DEVICE_MEMORY)
+                            .with_size(size)
+                            .with_memory_type_index(memory_type_index)
+                            .with_allocation_create_info(info),
+                    );
+                    continue;
+                }
+            }
+
+            match create(info) {
+                Ok(resource) => return Ok(Tiered { tier, resource }),
+                Err(error) if is_out_of_memory(&error) => last_error = Some(error),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            vma::Error::new(function, vk::Result::ERROR_OUT_OF_DEVICE_MEMORY).with_size(size)
+        }))
+    }
+}
+
+/// Checks whether `error` means that a different tier might still succeed
+fn is_out_of_memory(error: &vma::Error) -> bool {
+    matches!(
+        error.result(),
+        vk::Result::ERROR_OUT_OF_DEVICE_MEMORY | vk::Result::ERROR_OUT_OF_HOST_MEMORY
+    )
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use ash::vk;
+
+    use super::AllocationPolicy;
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::vma;
+
+    /// A device-local heap with memory type 0 and a host heap with memory type 1
+    fn device(device_local_size: vk::DeviceSize) -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(device_local_size, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .heap(256 << 20, vk::MemoryHeapFlags::empty())
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL)
+                .memory_type(
+                    1,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                ),
+        )
+    }
+
+    fn buffer_info(size: vk::DeviceSize) -> vk::BufferCreateInfo<'static> {
+        vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(vk::BufferUsageFlags::VERTEX_BUFFER | vk::BufferUsageFlags::TRANSFER_DST)
+    }
+
+    fn device_tier() -> vma::AllocationCreateInfo {
+        vma::AllocationCreateInfo::default()
+            .usage(vma::MemoryUsage::AUTO_PREFER_DEVICE)
+            .required_flags(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+    }
+
+    fn host_tier() -> vma::AllocationCreateInfo {
+        vma::AllocationCreateInfo::default()
+            .usage(vma::MemoryUsage::AUTO_PREFER_HOST)
+            .flags(vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE)
+    }
+
+    #[test]
+    fn full_device_heap_falls_back_to_host_tier() {
+        let device = device(256 << 20);
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        device.limit_heap(0, 1 << 20);
+        let policy = AllocationPolicy::new()
+            .tier(device_tier())
+            .tier(host_tier());
+
+        let first = unsafe { policy.create_buffer(&allocator, &buffer_info(512 << 10)) }.unwrap();
+        assert_eq!(first.tier, 0);
+        assert_eq!(first.resource.memory_type(), 0);
+
+        let second = unsafe { policy.create_buffer(&allocator, &buffer_info(1 << 20)) }.unwrap();
+        assert_eq!(second.tier, 1);
+        assert_eq!(second.resource.memory_type(), 1);
+        assert!(device.heap_usage(0) <= 1 << 20);
+        assert!(device.heap_usage(1) >= 1 << 20);
+
+        // without a fallback the error of the device tier is returned
+        let device_only = AllocationPolicy::new().tier(device_tier());
+        let error =
+            unsafe { device_only.create_buffer(&allocator, &buffer_info(1 << 20)) }.unwrap_err();
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+    }
+
+    #[test]
+    fn budget_rejection_falls_back_to_host_tier() {
+        // VMA estimates the budget as 80% of the heap without `VK_EXT_memory_budget`
+        let device = device(16 << 20);
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        let filler = unsafe {
+            AllocationPolicy::new()
+                .tier(device_tier())
+                .create_buffer(&allocator, &buffer_info(8 << 20))
+        }
+        .unwrap();
+        assert_eq!(filler.tier, 0);
+        let usage = device.heap_usage(0);
+
+        let policy = AllocationPolicy::new()
+            .tier(device_tier().flags(vma::AllocationCreateFlags::WITHIN_BUDGET))
+            .tier(host_tier());
+        let buffer = unsafe { policy.create_buffer(&allocator, &buffer_info(6 << 20)) }.unwrap();
+        assert_eq!(buffer.tier, 1);
+        assert_eq!(buffer.resource.memory_type(), 1);
+        assert_eq!(device.heap_usage(0), usage);
+
+        // a buffer that still fits into the budget stays in device memory
+        let small = unsafe { policy.create_buffer(&allocator, &buffer_info(1 << 20)) }.unwrap();
+        assert_eq!(small.tier, 0);
+    }
+}
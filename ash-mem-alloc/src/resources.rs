@@ -0,0 +1,371 @@
+use std::ffi::c_void;
+
+use ash::vk;
+
+use crate::{vma, AllocationOptions, Allocator};
+
+/// A [`vk::Buffer`] together with the memory bound to it.
+///
+/// The buffer and its allocation are destroyed with [`vma::destroy_buffer`] when this is dropped.
+/// The owner is responsible for making sure the device no longer uses the buffer at that point.
+#[derive(Debug)]
+pub struct AllocatedBuffer {
+    allocator: Allocator,
+    buffer: vk::Buffer,
+    allocation: vma::Allocation,
+}
+
+impl AllocatedBuffer {
+    /// Safe version of [`AllocatedBuffer::new`]
+    ///
+    /// # Panics
+    /// Panics if `buffer_create_info` has a `p_next` chain or uses [`vk::SharingMode::CONCURRENT`].
+    pub fn create(
+        allocator: &Allocator,
+        buffer_create_info: &vk::BufferCreateInfo,
+        options: &AllocationOptions,
+    ) -> Result<Self, vma::Error> {
+        crate::options::assert_plain_buffer_info(buffer_create_info);
+        unsafe { Self::new(allocator, buffer_create_info, options.create_info()) }
+    }
+
+    /// Creates a new buffer and binds freshly allocated memory to it.
+    ///
+    /// See [`vma::create_buffer`]
+    ///
+    /// # Safety
+    /// See [`Allocator::create_buffer`]
+    pub unsafe fn new(
+        allocator: &Allocator,
+        buffer_create_info: &vk::BufferCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<Self, vma::Error> {
+        let (buffer, allocation, _) =
+            allocator.create_buffer(buffer_create_info, allocation_create_info)?;
+
+        Ok(Self {
+            allocator: allocator.clone(),
+            buffer,
+            allocation,
+        })
+    }
+
+    /// Creates a new buffer whose memory is aligned to at least `min_alignment`.
+    ///
+    /// See [`vma::create_buffer_with_alignment`]
+    ///
+    /// # Safety
+    /// See [`Allocator::create_buffer`]
+    pub unsafe fn with_alignment(
+        allocator: &Allocator,
+        buffer_create_info: &vk::BufferCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+        min_alignment: vk::DeviceSize,
+    ) -> Result<Self, vma::Error> {
+        let (buffer, allocation, _) = allocator.create_buffer_with_alignment(
+            buffer_create_info,
+            allocation_create_info,
+            min_alignment,
+        )?;
+
+        Ok(Self {
+            allocator: allocator.clone(),
+            buffer,
+            allocation,
+        })
+    }
+
+    /// Takes ownership of a buffer and allocation created by `allocator`.
+    ///
+    /// # Safety
+    /// `buffer` and `allocation` must have been created together by `allocator`,
+    /// e.g. through [`vma::create_buffer`], and must not be destroyed by anyone else.
+    pub unsafe fn from_raw(
+        allocator: &Allocator,
+        buffer: vk::Buffer,
+        allocation: vma::Allocation,
+    ) -> Self {
+        Self {
+            allocator: allocator.clone(),
+            buffer,
+            allocation,
+        }
+    }
+
+    /// Releases ownership of the buffer and its allocation without destroying them.
+    pub fn into_raw(self) -> (vk::Buffer, vma::Allocation) {
+        let this = std::mem::ManuallyDrop::new(self);
+        // drop the allocator reference, but keep the buffer alive
+        drop(unsafe { std::ptr::read(&this.allocator) });
+        (this.buffer, this.allocation)
+    }
+
+    /// Returns the allocator that owns the memory of this buffer
+    pub fn allocator(&self) -> &Allocator {
+        &self.allocator
+    }
+
+    /// Returns the raw buffer handle
+    pub fn buffer(&self) -> vk::Buffer {
+        self.buffer
+    }
+
+    /// Returns the raw allocation handle
+    pub fn allocation(&self) -> vma::Allocation {
+        self.allocation
+    }
+
+    /// Returns up-to-date information about the allocation of this buffer.
+    ///
+    /// See [`vma::get_allocation_info`]
+    pub fn info(&self) -> vma::AllocationInfo<'_> {
+        unsafe { self.allocator.get_allocation_info(self.allocation) }
+    }
+
+    /// Returns the size of the allocation in bytes
+    pub fn size(&self) -> vk::DeviceSize {
+        self.info().size
+    }
+
+    /// Returns the offset of the allocation inside of its [`vk::DeviceMemory`] block
+    pub fn offset(&self) -> vk::DeviceSize {
+        self.info().offset
+    }
+
+    /// Returns the [`vk::DeviceMemory`] block the allocation lives in
+    pub fn device_memory(&self) -> vk::DeviceMemory {
+        self.info().device_memory
+    }
+
+    /// Returns the memory type index of the allocation
+    pub fn memory_type(&self) -> u32 {
+        self.info().memory_type
+    }
+
+    /// Returns the host pointer of the allocation if it is persistently mapped, or null otherwise.
+    ///
+    /// See [`vma::AllocationCreateFlags::MAPPED`]
+    pub fn mapped_ptr(&self) -> *mut c_void {
+        self.info().p_mapped_data
+    }
+}
+
+impl Drop for AllocatedBuffer {
+    fn drop(&mut self) {
+        unsafe { self.allocator.destroy_buffer(self.buffer, self.allocation) };
+    }
+}
+
+/// A [`vk::Image`] together with the memory bound to it.
+///
+/// The image and its allocation are destroyed with [`vma::destroy_image`] when this is dropped.
+/// The owner is responsible for making sure the device no longer uses the image at that point.
+#[derive(Debug)]
+pub struct AllocatedImage {
+    allocator: Allocator,
+    image: vk::Image,
+    allocation: vma::Allocation,
+}
+
+impl AllocatedImage {
+    /// Safe version of [`AllocatedImage::new`]
+    ///
+    /// # Panics
+    /// Panics if `image_create_info` has a `p_next` chain or uses [`vk::SharingMode::CONCURRENT`].
+    pub fn create(
+        allocator: &Allocator,
+        image_create_info: &vk::ImageCreateInfo,
+        options: &AllocationOptions,
+    ) -> Result<Self, vma::Error> {
+        crate::options::assert_plain_image_info(image_create_info);
+        unsafe { Self::new(allocator, image_create_info, options.create_info()) }
+    }
+
+    /// Creates a new image and binds freshly allocated memory to it.
+    ///
+    /// See [`vma::create_image`]
+    ///
+    /// # Safety
+    /// See [`Allocator::create_image`]
+    pub unsafe fn new(
+        allocator: &Allocator,
+        image_create_info: &vk::ImageCreateInfo,
+        allocation_create_info: &vma::AllocationCreateInfo,
+    ) -> Result<Self, vma::Error> {
+        let (image, allocation, _) =
+            allocator.create_image(image_create_info, allocation_create_info)?;
+
+        Ok(Self {
+            allocator: allocator.clone(),
+            image,
+            allocation,
+        })
+    }
+
+    /// Takes ownership of an image and allocation created by `allocator`.
+    ///
+    /// # Safety
+    /// `image` and `allocation` must have been created together by `allocator`,
+    /// e.g. through [`vma::create_image`], and must not be destroyed by anyone else.
+    pub unsafe fn from_raw(
+        allocator: &Allocator,
+        image: vk::Image,
+        allocation: vma::Allocation,
+    ) -> Self {
+        Self {
+            allocator: allocator.clone(),
+            image,
+            allocation,
+        }
+    }
+
+    /// Releases ownership of the image and its allocation without destroying them.
+    pub fn into_raw(self) -> (vk::Image, vma::Allocation) {
+        let this = std::mem::ManuallyDrop::new(self);
+        // drop the allocator reference, but keep the image alive
+        drop(unsafe { std::ptr::read(&this.allocator) });
+        (this.image, this.allocation)
+    }
+
+    /// Returns the allocator that owns the memory of this image
+    pub fn allocator(&self) -> &Allocator {
+        &self.allocator
+    }
+
+    /// Returns the raw image handle
+    pub fn image(&self) -> vk::Image {
+        self.image
+    }
+
+    /// Returns the raw allocation handle
+    pub fn allocation(&self) -> vma::Allocation {
+        self.allocation
+    }
+
+    /// Returns up-to-date information about the allocation of this image.
+    ///
+    /// See [`vma::get_allocation_info`]
+    pub fn info(&self) -> vma::AllocationInfo<'_> {
+        unsafe { self.allocator.get_allocation_info(self.allocation) }
+    }
+
+    /// Returns the size of the allocation in bytes
+    pub fn size(&self) -> vk::DeviceSize {
+        self.info().size
+    }
+
+    /// Returns the offset of the allocation inside of its [`vk::DeviceMemory`] block
+    pub fn offset(&self) -> vk::DeviceSize {
+        self.info().offset
+    }
+
+    /// Returns the [`vk::DeviceMemory`] block the allocation lives in
+    pub fn device_memory(&self) -> vk::DeviceMemory {
+        self.info().device_memory
+    }
+
+    /// Returns the memory type index of the allocation
+    pub fn memory_type(&self) -> u32 {
+        self.info().memory_type
+    }
+
+    /// Returns the host pointer of the allocation if it is persistently mapped, or null otherwise.
+    ///
+    /// See [`vma::AllocationCreateFlags::MAPPED`]
+    pub fn mapped_ptr(&self) -> *mut c_void {
+        self.info().p_mapped_data
+    }
+}
+
+impl Drop for AllocatedImage {
+    fn drop(&mut self) {
+        unsafe { self.allocator.destroy_image(self.image, self.allocation) };
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use ash::vk;
+
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::{vma, AllocatedBuffer, AllocatedImage, AllocationOptions};
+
+    fn device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL),
+        )
+    }
+
+    #[test]
+    fn buffer_is_destroyed_on_drop() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(1024)
+            .usage(vk::BufferUsageFlags::VERTEX_BUFFER);
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::AUTO);
+        let buffer = AllocatedBuffer::create(&allocator, &buffer_info, &options).unwrap();
+
+        assert_eq!(device.buffer_count(), 1);
+        assert_eq!(device.memory_object_count(), 1);
+        assert!(buffer.size() >= 1024);
+
+        drop(buffer);
+        assert_eq!(device.buffer_count(), 0);
+
+        // VMA may keep an empty block around until the allocator is destroyed
+        drop(allocator);
+        assert_eq!(device.memory_object_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "need the `unsafe` functions")]
+    fn create_rejects_p_next_chains() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        let mut external = vk::ExternalMemoryBufferCreateInfo::default();
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(1024)
+            .usage(vk::BufferUsageFlags::VERTEX_BUFFER)
+            .push_next(&mut external);
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::AUTO);
+        let _ = AllocatedBuffer::create(&allocator, &buffer_info, &options);
+    }
+
+    #[test]
+    fn image_is_destroyed_on_drop() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(vk::Format::R8G8B8A8_UNORM)
+            .extent(vk::Extent3D {
+                width: 64,
+                height: 64,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .usage(vk::ImageUsageFlags::SAMPLED);
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::AUTO);
+        let image = AllocatedImage::create(&allocator, &image_info, &options).unwrap();
+        assert_eq!(device.image_count(), 1);
+
+        drop(image);
+        drop(allocator);
+        assert_eq!(device.image_count(), 0);
+        assert_eq!(device.memory_object_count(), 0);
+    }
+}
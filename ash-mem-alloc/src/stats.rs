@@ -0,0 +1,61 @@
+use ash::vk;
+
+use crate::vma;
+
+/// Builds a JSON dump of the current state of `allocator`.
+///
+/// Builds the string with [`vma::build_stats_string`], copies it and frees the original
+/// with [`vma::free_stats_string`].
+///
+/// If `detailed_map` is true, the dump also contains every block with all of its allocations and free ranges.
+///
+/// # Safety
+/// `allocator` must be a live allocator.
+pub unsafe fn stats_string(allocator: vma::Allocator, detailed_map: bool) -> String {
+    let raw = vma::build_stats_string(allocator, detailed_map as vk::Bool32);
+    copy_and_free(raw, |raw| vma::free_stats_string(allocator, raw))
+}
+
+/// Builds a JSON dump of the current state of `allocator` with [`stats_string`] and parses it.
+///
+/// # Safety
+/// `allocator` must be a live allocator.
+#[cfg(feature = "json")]
+pub unsafe fn parse_stats(
+    allocator: vma::Allocator,
+    detailed_map: bool,
+) -> serde_json::Result<crate::json::Stats> {
+    serde_json::from_str(&stats_string(allocator, detailed_map))
+}
+
+/// Builds a JSON dump of the current state of `virtual_block`.
+///
+/// Builds the string with [`vma::build_virtual_block_stats_string`], copies it and frees the original
+/// with [`vma::free_virtual_block_stats_string`].
+///
+/// If `detailed_map` is true, the dump also contains all allocations and free ranges.
+///
+/// # Safety
+/// `virtual_block` must be a live virtual block.
+pub unsafe fn virtual_block_stats_string(
+    virtual_block: vma::VirtualBlock,
+    detailed_map: bool,
+) -> String {
+    let raw = vma::build_virtual_block_stats_string(virtual_block, detailed_map as vk::Bool32);
+    copy_and_free(raw, |raw| {
+        vma::free_virtual_block_stats_string(virtual_block, raw)
+    })
+}
+
+/// Copies a VMA owned string into a rust string and hands it back to VMA through `free`
+unsafe fn copy_and_free(
+    raw: *mut std::ffi::c_char,
+    free: impl FnOnce(*mut std::ffi::c_char),
+) -> String {
+    if raw.is_null() {
+        return String::new();
+    }
+    let string = std::ffi::CStr::from_ptr(raw).to_string_lossy().into_owned();
+    free(raw);
+    string
+}
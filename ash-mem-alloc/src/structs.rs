@@ -365,6 +365,7 @@ impl AllocatorInfo {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "\\brief Calculated statistics of memory usage e.g. in a specific memory type, heap, custom pool, or total.\n\nThese are fast to calculate.\nSee functions: vmaGetHeapBudgets(), vmaGetPoolStatistics()."]
 pub struct Statistics {
     #[doc = "\\brief Number of `VkDeviceMemory` objects - Vulkan memory blocks allocated."]
@@ -401,6 +402,7 @@ impl Statistics {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "\\brief More detailed statistics than #VmaStatistics.\n\nThese are slower to calculate. Use for debugging purposes.\nSee functions: vmaCalculateStatistics(), vmaCalculatePoolStatistics().\n\nPrevious version of the statistics API provided averages, but they have been removed\nbecause they can be easily calculated as:\n\n\\code\nVkDeviceSize allocationSizeAvg = detailedStats.statistics.allocationBytes / detailedStats.statistics.allocationCount;\nVkDeviceSize unusedBytes = detailedStats.statistics.blockBytes - detailedStats.statistics.allocationBytes;\nVkDeviceSize unusedRangeSizeAvg = unusedBytes / detailedStats.unusedRangeCount;\n\\endcode"]
 pub struct DetailedStatistics {
     #[doc = "Basic statistics."]
@@ -449,6 +451,7 @@ impl DetailedStatistics {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "\\brief  General statistics from current state of the Allocator -\ntotal memory usage across all memory heaps and types.\n\nThese are slower to calculate. Use for debugging purposes.\nSee function vmaCalculateStatistics()."]
 pub struct TotalStatistics {
     pub memory_type: [crate::vma::DetailedStatistics; 32usize],
@@ -476,6 +479,7 @@ impl TotalStatistics {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "\\brief Statistics of current memory usage and available budget for a specific memory heap.\n\nThese are fast to calculate.\nSee function vmaGetHeapBudgets()."]
 pub struct Budget {
     #[doc = "\\brief Statistics fetched from the library."]
@@ -840,6 +844,7 @@ impl<'a> DefragmentationPassMoveInfo<'a> {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "Statistics returned for defragmentation process in function vmaEndDefragmentation()."]
 pub struct DefragmentationStats {
     #[doc = "Total number of bytes that have been copied while moving allocations to different places."]
@@ -910,6 +915,7 @@ impl<'a> VirtualBlockCreateInfo<'a> {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "Parameters of created virtual allocation to be passed to vmaVirtualAllocate()."]
 pub struct VirtualAllocationCreateInfo {
     #[doc = "\\brief Size of the allocation.\n\n Cannot be zero."]
@@ -919,6 +925,7 @@ pub struct VirtualAllocationCreateInfo {
     #[doc = "\\brief Use combination of #VmaVirtualAllocationCreateFlagBits."]
     pub flags: crate::vma::VirtualAllocationCreateFlags,
     #[doc = "\\brief Custom pointer to be associated with the allocation. Optional.\n\n It can be any value and can be used for user-defined purposes. It can be fetched or changed later."]
+    #[cfg_attr(feature = "serde", serde(skip, default = "::std::ptr::null_mut"))]
     pub p_user_data: *mut ::std::ffi::c_void,
 }
 impl Default for VirtualAllocationCreateInfo {
@@ -946,6 +953,7 @@ impl VirtualAllocationCreateInfo {
 }
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[doc = "Parameters of an existing virtual allocation, returned by vmaGetVirtualAllocationInfo()."]
 pub struct VirtualAllocationInfo {
     #[doc = "\\brief Offset of the allocation.\n\n Offset at which the allocation was made."]
@@ -953,6 +961,7 @@ pub struct VirtualAllocationInfo {
     #[doc = "\\brief Size of the allocation.\n\n Same value as passed in VmaVirtualAllocationCreateInfo::size."]
     pub size: vk::DeviceSize,
     #[doc = "\\brief Custom pointer associated with the allocation.\n\n Same value as passed in VmaVirtualAllocationCreateInfo::pUserData or to vmaSetVirtualAllocationUserData()."]
+    #[cfg_attr(feature = "serde", serde(skip, default = "::std::ptr::null_mut"))]
     pub p_user_data: *mut ::std::ffi::c_void,
 }
 impl Default for VirtualAllocationInfo {
@@ -0,0 +1,1155 @@
+use std::alloc::Layout;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::ops::Range;
+use std::sync::{Mutex, MutexGuard};
+
+use ash::vk::{self, Handle};
+
+use super::MemoryTopology;
+use crate::{vma, Allocator};
+
+/// Alignment of the host memory backing every fake `VkDeviceMemory`
+const MEMORY_ALIGNMENT: usize = 4096;
+/// Alignment reported for buffer memory requirements
+const BUFFER_ALIGNMENT: vk::DeviceSize = 256;
+/// Alignment reported for image memory requirements
+const IMAGE_ALIGNMENT: vk::DeviceSize = 4096;
+/// Size of a single texel, every image format is treated as having the same size
+const TEXEL_SIZE: vk::DeviceSize = 4;
+
+/// A software stand-in for a Vulkan instance, physical device and device.
+///
+/// Implements every function in [`vma::VulkanFunctions`] in Rust, backing device memory with host memory.
+/// This allows running an allocator on machines without a GPU, e.g. in CI.
+///
+/// All dispatchable handles (instance, physical device, device and command buffer) refer to the same fake device.
+/// Functions that VMA does not need are not implemented, calling them through [`FakeDevice::instance`]
+/// or [`FakeDevice::device`] panics.
+///
+/// Failures can be injected into `vkAllocateMemory` and `vkMapMemory` to exercise out-of-memory paths,
+/// see [`FakeDevice::fail_nth_allocation`], [`FakeDevice::limit_heap`] and [`FakeDevice::fail_map_memory`].
+/// Flushed and invalidated ranges are recorded, see [`FakeDevice::take_flushed_ranges`].
+///
+/// The bookkeeping of a fake device is intentionally leaked, so that its handles stay valid
+/// for as long as anything refers to them. Device memory is released again by `vkFreeMemory`.
+#[derive(Clone, Copy)]
+pub struct FakeDevice {
+    state: &'static State,
+}
+
+impl FakeDevice {
+    /// Creates a new fake device reporting the memory heaps and types of `topology`
+    pub fn new(topology: &MemoryTopology) -> Self {
+        let properties = vk::PhysicalDeviceProperties {
+            api_version: vk::API_VERSION_1_3,
+            device_type: vk::PhysicalDeviceType::CPU,
+            limits: vk::PhysicalDeviceLimits {
+                max_memory_allocation_count: 4096,
+                buffer_image_granularity: 1,
+                non_coherent_atom_size: 64,
+                min_memory_map_alignment: MEMORY_ALIGNMENT,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .device_name(c"ash-mem-alloc fake device")
+        .expect("device name fits into VK_MAX_PHYSICAL_DEVICE_NAME_SIZE");
+
+        let state = Box::leak(Box::new(State {
+            properties,
+            memory_properties: topology.memory_properties(),
+            objects: Mutex::new(Objects {
+                next_handle: 1,
+                heap_usage: vec![0; topology.heaps().len()],
+                ..Default::default()
+            }),
+        }));
+        Self { state }
+    }
+
+    /// Returns the fake instance handle
+    pub fn instance_handle(&self) -> vk::Instance {
+        vk::Instance::from_raw(self.state as *const State as u64)
+    }
+
+    /// Returns the fake physical device handle
+    pub fn physical_device(&self) -> vk::PhysicalDevice {
+        vk::PhysicalDevice::from_raw(self.state as *const State as u64)
+    }
+
+    /// Returns the fake device handle
+    pub fn device_handle(&self) -> vk::Device {
+        vk::Device::from_raw(self.state as *const State as u64)
+    }
+
+    /// Returns a fake command buffer.
+    ///
+    /// Commands recorded into it are executed immediately.
+    pub fn command_buffer(&self) -> vk::CommandBuffer {
+        vk::CommandBuffer::from_raw(self.state as *const State as u64)
+    }
+
+    /// Returns an entry loader whose functions are served by this fake device
+    pub fn entry(&self) -> ash::Entry {
+        let static_fn = ash::StaticFn {
+            get_instance_proc_addr,
+        };
+        unsafe { ash::Entry::from_static_fn(static_fn) }
+    }
+
+    /// Returns an instance loader whose functions are served by this fake device
+    pub fn instance(&self) -> ash::Instance {
+        let static_fn = ash::StaticFn {
+            get_instance_proc_addr,
+        };
+        unsafe { ash::Instance::load(&static_fn, self.instance_handle()) }
+    }
+
+    /// Returns a device loader whose functions are served by this fake device
+    pub fn device(&self) -> ash::Device {
+        unsafe { ash::Device::load(self.instance().fp_v1_0(), self.device_handle()) }
+    }
+
+    /// Returns a fully populated function table implemented by fake devices
+    pub fn vulkan_functions(&self) -> vma::VulkanFunctions {
+        vma::VulkanFunctions::default()
+            .get_instance_proc_addr(Some(get_instance_proc_addr))
+            .get_device_proc_addr(Some(get_device_proc_addr))
+            .get_physical_device_properties(Some(get_physical_device_properties))
+            .get_physical_device_memory_properties(Some(get_physical_device_memory_properties))
+            .allocate_memory(Some(allocate_memory))
+            .free_memory(Some(free_memory))
+            .map_memory(Some(map_memory))
+            .unmap_memory(Some(unmap_memory))
+            .flush_mapped_memory_ranges(Some(flush_mapped_memory_ranges))
+            .invalidate_mapped_memory_ranges(Some(invalidate_mapped_memory_ranges))
+            .bind_buffer_memory(Some(bind_buffer_memory))
+            .bind_image_memory(Some(bind_image_memory))
+            .get_buffer_memory_requirements(Some(get_buffer_memory_requirements))
+            .get_image_memory_requirements(Some(get_image_memory_requirements))
+            .create_buffer(Some(create_buffer))
+            .destroy_buffer(Some(destroy_buffer))
+            .create_image(Some(create_image))
+            .destroy_image(Some(destroy_image))
+            .cmd_copy_buffer(Some(cmd_copy_buffer))
+            .get_buffer_memory_requirements_2_khr(Some(get_buffer_memory_requirements_2))
+            .get_image_memory_requirements_2_khr(Some(get_image_memory_requirements_2))
+            .bind_buffer_memory_2_khr(Some(bind_buffer_memory_2))
+            .bind_image_memory_2_khr(Some(bind_image_memory_2))
+            .get_physical_device_memory_properties_2_khr(Some(
+                get_physical_device_memory_properties_2,
+            ))
+            .get_device_buffer_memory_requirements(Some(get_device_buffer_memory_requirements))
+            .get_device_image_memory_requirements(Some(get_device_image_memory_requirements))
+    }
+
+    /// Creates an allocator for this fake device.
+    ///
+    /// The handles and the function table of `create_info` are replaced by the ones of this device.
+    /// [`vma::AllocatorCreateFlags::EXTERNALLY_SYNCHRONIZED`] is ignored, as an [`Allocator`] can be shared between threads.
+    pub fn create_allocator(
+        &self,
+        create_info: &vma::AllocatorCreateInfo,
+    ) -> Result<Allocator, vma::Error> {
+        let functions = self.vulkan_functions();
+        let create_info = create_info
+            .flags(create_info.flags & !vma::AllocatorCreateFlags::EXTERNALLY_SYNCHRONIZED)
+            .vulkan_functions(&functions);
+        unsafe {
+            Allocator::new(
+                &self.instance(),
+                &self.device(),
+                self.physical_device(),
+                &create_info,
+            )
+        }
+    }
+
+    /// Returns the memory properties reported by this device
+    pub fn memory_properties(&self) -> &vk::PhysicalDeviceMemoryProperties {
+        &self.state.memory_properties
+    }
+
+    /// Returns the number of bytes currently allocated from heap `heap_index`
+    pub fn heap_usage(&self, heap_index: u32) -> vk::DeviceSize {
+        self.state.objects().heap_usage[heap_index as usize]
+    }
+
+    /// Returns the number of live `VkDeviceMemory` objects
+    pub fn memory_object_count(&self) -> usize {
+        self.state.objects().memory.len()
+    }
+
+    /// Returns the number of live buffers
+    pub fn buffer_count(&self) -> usize {
+        self.state.objects().buffers.len()
+    }
+
+    /// Returns the number of live images
+    pub fn image_count(&self) -> usize {
+        self.state.objects().images.len()
+    }
+
+    /// Returns the number of `vkAllocateMemory` calls so far, including failed ones
+    pub fn allocation_calls(&self) -> u64 {
+        self.state.objects().allocation_calls
+    }
+
+    /// Returns the ranges passed to `vkFlushMappedMemoryRanges` since the last call, in call order.
+    ///
+    /// `VK_WHOLE_SIZE` is resolved to the end of the memory object.
+    pub fn take_flushed_ranges(&self) -> Vec<(vk::DeviceMemory, Range<vk::DeviceSize>)> {
+        std::mem::take(&mut self.state.objects().flushed_ranges)
+    }
+
+    /// Returns the ranges passed to `vkInvalidateMappedMemoryRanges` since the last call, in call order.
+    ///
+    /// `VK_WHOLE_SIZE` is resolved to the end of the memory object.
+    pub fn take_invalidated_ranges(&self) -> Vec<(vk::DeviceMemory, Range<vk::DeviceSize>)> {
+        std::mem::take(&mut self.state.objects().invalidated_ranges)
+    }
+
+    /// Makes the `n`-th call to `vkAllocateMemory` from now on fail with `VK_ERROR_OUT_OF_DEVICE_MEMORY`,
+    /// `1` being the very next call.
+    ///
+    /// Note that VMA retries failed block allocations with smaller block sizes,
+    /// so a single failure does not necessarily surface as an error.
+    /// Use [`FakeDevice::fail_allocations_after`] to make every following call fail.
+    pub fn fail_nth_allocation(&self, n: u64) {
+        assert!(n > 0, "allocation calls are counted starting at 1");
+        let mut objects = self.state.objects();
+        objects.faults.allocations_until_failure = Some(n);
+        objects.faults.keep_failing = false;
+    }
+
+    /// Lets the next `n` calls to `vkAllocateMemory` succeed and makes every call after them
+    /// fail with `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+    pub fn fail_allocations_after(&self, n: u64) {
+        let mut objects = self.state.objects();
+        objects.faults.allocations_until_failure = Some(n.saturating_add(1));
+        objects.faults.keep_failing = true;
+    }
+
+    /// Makes `vkAllocateMemory` fail with `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+    /// once more than `max_bytes` would be allocated from heap `heap_index`.
+    ///
+    /// Unlike the size of the heap, the limit is not visible to VMA.
+    pub fn limit_heap(&self, heap_index: u32, max_bytes: vk::DeviceSize) {
+        assert!(
+            heap_index < self.state.memory_properties.memory_heap_count,
+            "heap {heap_index} does not exist"
+        );
+        let mut objects = self.state.objects();
+        objects.faults.heap_limits.insert(heap_index, max_bytes);
+    }
+
+    /// Makes every call to `vkMapMemory` fail with `VK_ERROR_MEMORY_MAP_FAILED` while `fail` is set
+    pub fn fail_map_memory(&self, fail: bool) {
+        self.state.objects().faults.fail_map = fail;
+    }
+
+    /// Removes all injected failures
+    pub fn clear_faults(&self) {
+        self.state.objects().faults = Faults::default();
+    }
+}
+
+impl std::fmt::Debug for FakeDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let objects = self.state.objects();
+        f.debug_struct("FakeDevice")
+            .field("handle", &self.device_handle())
+            .field("heap_usage", &objects.heap_usage)
+            .field("memory_objects", &objects.memory.len())
+            .field("buffers", &objects.buffers.len())
+            .field("images", &objects.images.len())
+            .finish()
+    }
+}
+
+/// Everything a fake device knows, all dispatchable handles point to it
+struct State {
+    properties: vk::PhysicalDeviceProperties,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    objects: Mutex<Objects>,
+}
+
+impl State {
+    /// Resolves a dispatchable handle to the fake device it was handed out by
+    unsafe fn from_handle<'a>(handle: impl Handle) -> &'a Self {
+        &*(handle.as_raw() as *const Self)
+    }
+
+    /// Locks the objects of this device, ignoring poisoning
+    fn objects(&self) -> MutexGuard<'_, Objects> {
+        self.objects.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Live objects of a fake device
+#[derive(Default)]
+struct Objects {
+    next_handle: u64,
+    allocation_calls: u64,
+    faults: Faults,
+    heap_usage: Vec<vk::DeviceSize>,
+    memory: HashMap<u64, Memory>,
+    buffers: HashMap<u64, Resource>,
+    images: HashMap<u64, Resource>,
+    flushed_ranges: Vec<(vk::DeviceMemory, Range<vk::DeviceSize>)>,
+    invalidated_ranges: Vec<(vk::DeviceMemory, Range<vk::DeviceSize>)>,
+}
+
+/// Failures injected into a fake device
+#[derive(Default)]
+struct Faults {
+    /// Number of `vkAllocateMemory` calls until one fails, `1` failing the next call
+    allocations_until_failure: Option<u64>,
+    /// Whether all calls after the failing one fail as well
+    keep_failing: bool,
+    /// Maximum number of bytes that may be allocated from a heap
+    heap_limits: HashMap<u32, vk::DeviceSize>,
+    /// Whether `vkMapMemory` fails
+    fail_map: bool,
+}
+
+impl Faults {
+    /// Counts a call to `vkAllocateMemory` and checks whether it should fail
+    fn allocation_fails(&mut self) -> bool {
+        match &mut self.allocations_until_failure {
+            Some(1) => {
+                if !self.keep_failing {
+                    self.allocations_until_failure = None;
+                }
+                true
+            }
+            Some(n) => {
+                *n -= 1;
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+impl Objects {
+    /// Returns a new, unique non-dispatchable handle
+    fn next_handle(&mut self) -> u64 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        handle
+    }
+
+    /// Returns the host address backing `size` bytes at `offset` into the memory bound to `buffer`,
+    /// or `None` if the range does not fit into the buffer or its memory
+    fn buffer_range(
+        &self,
+        buffer: vk::Buffer,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Option<*mut u8> {
+        let buffer = self.buffers.get(&buffer.as_raw())?;
+        let (memory, memory_offset) = buffer.binding?;
+        let memory = self.memory.get(&memory.as_raw())?;
+
+        let end = offset.checked_add(size)?;
+        if end > buffer.requirements.size || memory_offset.checked_add(end)? > memory.size {
+            return None;
+        }
+        Some(unsafe { memory.data.add((memory_offset + offset) as usize) })
+    }
+
+    /// Resolves `ranges` to byte ranges within their memory objects
+    fn mapped_ranges(
+        &self,
+        ranges: &[vk::MappedMemoryRange<'_>],
+    ) -> Vec<(vk::DeviceMemory, Range<vk::DeviceSize>)> {
+        ranges
+            .iter()
+            .map(|range| {
+                let end = match range.size {
+                    vk::WHOLE_SIZE => self
+                        .memory
+                        .get(&range.memory.as_raw())
+                        .map_or(range.offset, |memory| memory.size),
+                    size => range.offset + size,
+                };
+                (range.memory, range.offset..end)
+            })
+            .collect()
+    }
+}
+
+/// Host memory backing a fake `VkDeviceMemory`
+struct Memory {
+    data: *mut u8,
+    layout: Layout,
+    size: vk::DeviceSize,
+    memory_type_index: u32,
+    mapped: bool,
+}
+
+// the backing memory is only ever accessed through the device mutex or by the mapping owner
+unsafe impl Send for Memory {}
+
+/// A fake buffer or image
+struct Resource {
+    requirements: vk::MemoryRequirements,
+    binding: Option<(vk::DeviceMemory, vk::DeviceSize)>,
+}
+
+impl State {
+    /// Memory type bits of all types that are neither protected nor lazily allocated
+    fn buffer_memory_type_bits(&self) -> u32 {
+        self.memory_type_bits(|flags| {
+            !flags.intersects(
+                vk::MemoryPropertyFlags::PROTECTED | vk::MemoryPropertyFlags::LAZILY_ALLOCATED,
+            )
+        })
+    }
+
+    /// Memory type bits of all types that are not protected,
+    /// lazily allocated types are only allowed for transient attachments
+    fn image_memory_type_bits(&self, usage: vk::ImageUsageFlags) -> u32 {
+        let transient = usage.contains(vk::ImageUsageFlags::TRANSIENT_ATTACHMENT);
+        self.memory_type_bits(|flags| {
+            !flags.contains(vk::MemoryPropertyFlags::PROTECTED)
+                && (transient || !flags.contains(vk::MemoryPropertyFlags::LAZILY_ALLOCATED))
+        })
+    }
+
+    fn memory_type_bits(&self, filter: impl Fn(vk::MemoryPropertyFlags) -> bool) -> u32 {
+        let types = &self.memory_properties.memory_types
+            [..self.memory_properties.memory_type_count as usize];
+        types
+            .iter()
+            .enumerate()
+            .filter(|(_, ty)| filter(ty.property_flags))
+            .fold(0, |bits, (i, _)| bits | (1 << i))
+    }
+
+    fn buffer_requirements(&self, info: &vk::BufferCreateInfo) -> vk::MemoryRequirements {
+        vk::MemoryRequirements {
+            size: info.size.next_multiple_of(4),
+            alignment: BUFFER_ALIGNMENT,
+            memory_type_bits: self.buffer_memory_type_bits(),
+        }
+    }
+
+    fn image_requirements(&self, info: &vk::ImageCreateInfo) -> vk::MemoryRequirements {
+        let extent = info.extent;
+        let texels: vk::DeviceSize = (0..info.mip_levels.max(1))
+            .map(|level| {
+                let dim = |d: u32| (d >> level).max(1) as vk::DeviceSize;
+                dim(extent.width) * dim(extent.height) * dim(extent.depth)
+            })
+            .sum();
+        let size = texels * info.array_layers.max(1) as vk::DeviceSize * TEXEL_SIZE;
+        vk::MemoryRequirements {
+            size: size.next_multiple_of(IMAGE_ALIGNMENT),
+            alignment: IMAGE_ALIGNMENT,
+            memory_type_bits: self.image_memory_type_bits(info.usage),
+        }
+    }
+}
+
+/// Looks up the fake implementation of the Vulkan function `name`
+fn lookup(name: &CStr) -> vk::PFN_vkVoidFunction {
+    macro_rules! functions {
+        ($($name:literal => $function:ident),* $(,)?) => {
+            match name.to_bytes() {
+                $($name => Some(unsafe {
+                    std::mem::transmute::<*const (), unsafe extern "system" fn()>($function as *const ())
+                }),)*
+                _ => None,
+            }
+        };
+    }
+
+    functions! {
+        b"vkGetInstanceProcAddr" => get_instance_proc_addr,
+        b"vkGetDeviceProcAddr" => get_device_proc_addr,
+        b"vkGetPhysicalDeviceProperties" => get_physical_device_properties,
+        b"vkGetPhysicalDeviceMemoryProperties" => get_physical_device_memory_properties,
+        b"vkGetPhysicalDeviceMemoryProperties2" => get_physical_device_memory_properties_2,
+        b"vkGetPhysicalDeviceMemoryProperties2KHR" => get_physical_device_memory_properties_2,
+        b"vkAllocateMemory" => allocate_memory,
+        b"vkFreeMemory" => free_memory,
+        b"vkMapMemory" => map_memory,
+        b"vkUnmapMemory" => unmap_memory,
+        b"vkFlushMappedMemoryRanges" => flush_mapped_memory_ranges,
+        b"vkInvalidateMappedMemoryRanges" => invalidate_mapped_memory_ranges,
+        b"vkBindBufferMemory" => bind_buffer_memory,
+        b"vkBindImageMemory" => bind_image_memory,
+        b"vkBindBufferMemory2" => bind_buffer_memory_2,
+        b"vkBindBufferMemory2KHR" => bind_buffer_memory_2,
+        b"vkBindImageMemory2" => bind_image_memory_2,
+        b"vkBindImageMemory2KHR" => bind_image_memory_2,
+        b"vkGetBufferMemoryRequirements" => get_buffer_memory_requirements,
+        b"vkGetImageMemoryRequirements" => get_image_memory_requirements,
+        b"vkGetBufferMemoryRequirements2" => get_buffer_memory_requirements_2,
+        b"vkGetBufferMemoryRequirements2KHR" => get_buffer_memory_requirements_2,
+        b"vkGetImageMemoryRequirements2" => get_image_memory_requirements_2,
+        b"vkGetImageMemoryRequirements2KHR" => get_image_memory_requirements_2,
+        b"vkGetDeviceBufferMemoryRequirements" => get_device_buffer_memory_requirements,
+        b"vkGetDeviceBufferMemoryRequirementsKHR" => get_device_buffer_memory_requirements,
+        b"vkGetDeviceImageMemoryRequirements" => get_device_image_memory_requirements,
+        b"vkGetDeviceImageMemoryRequirementsKHR" => get_device_image_memory_requirements,
+        b"vkCreateBuffer" => create_buffer,
+        b"vkDestroyBuffer" => destroy_buffer,
+        b"vkCreateImage" => create_image,
+        b"vkDestroyImage" => destroy_image,
+        b"vkCmdCopyBuffer" => cmd_copy_buffer,
+    }
+}
+
+unsafe extern "system" fn get_instance_proc_addr(
+    _instance: vk::Instance,
+    p_name: *const c_char,
+) -> vk::PFN_vkVoidFunction {
+    lookup(CStr::from_ptr(p_name))
+}
+
+unsafe extern "system" fn get_device_proc_addr(
+    _device: vk::Device,
+    p_name: *const c_char,
+) -> vk::PFN_vkVoidFunction {
+    lookup(CStr::from_ptr(p_name))
+}
+
+unsafe extern "system" fn get_physical_device_properties(
+    physical_device: vk::PhysicalDevice,
+    p_properties: *mut vk::PhysicalDeviceProperties,
+) {
+    *p_properties = State::from_handle(physical_device).properties;
+}
+
+unsafe extern "system" fn get_physical_device_memory_properties(
+    physical_device: vk::PhysicalDevice,
+    p_memory_properties: *mut vk::PhysicalDeviceMemoryProperties,
+) {
+    *p_memory_properties = State::from_handle(physical_device).memory_properties;
+}
+
+unsafe extern "system" fn get_physical_device_memory_properties_2(
+    physical_device: vk::PhysicalDevice,
+    p_memory_properties: *mut vk::PhysicalDeviceMemoryProperties2<'_>,
+) {
+    let state = State::from_handle(physical_device);
+    (*p_memory_properties).memory_properties = state.memory_properties;
+
+    // report the whole heap as budget and the allocated bytes as usage
+    let mut next = (*p_memory_properties).p_next as *mut vk::BaseOutStructure<'_>;
+    while !next.is_null() {
+        if (*next).s_type == vk::StructureType::PHYSICAL_DEVICE_MEMORY_BUDGET_PROPERTIES_EXT {
+            let budget = &mut *(next as *mut vk::PhysicalDeviceMemoryBudgetPropertiesEXT<'_>);
+            let objects = state.objects();
+            for (i, usage) in objects.heap_usage.iter().enumerate() {
+                budget.heap_budget[i] = state.memory_properties.memory_heaps[i].size;
+                budget.heap_usage[i] = *usage;
+            }
+        }
+        next = (*next).p_next;
+    }
+}
+
+unsafe extern "system" fn allocate_memory(
+    device: vk::Device,
+    p_allocate_info: *const vk::MemoryAllocateInfo<'_>,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+    p_memory: *mut vk::DeviceMemory,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let info = &*p_allocate_info;
+    if info.memory_type_index >= state.memory_properties.memory_type_count {
+        return vk::Result::ERROR_OUT_OF_DEVICE_MEMORY;
+    }
+    let heap_index =
+        state.memory_properties.memory_types[info.memory_type_index as usize].heap_index as usize;
+    let heap_size = state.memory_properties.memory_heaps[heap_index].size;
+
+    let mut objects = state.objects();
+    objects.allocation_calls += 1;
+    if objects.faults.allocation_fails() {
+        return vk::Result::ERROR_OUT_OF_DEVICE_MEMORY;
+    }
+
+    let limit = match objects.faults.heap_limits.get(&(heap_index as u32)) {
+        Some(&limit) => limit.min(heap_size),
+        None => heap_size,
+    };
+    // sizes that overflow can not fit into any heap either
+    match objects.heap_usage[heap_index].checked_add(info.allocation_size) {
+        Some(usage) if usage <= limit => {}
+        _ => return vk::Result::ERROR_OUT_OF_DEVICE_MEMORY,
+    }
+
+    let Ok(layout) =
+        Layout::from_size_align(info.allocation_size.max(1) as usize, MEMORY_ALIGNMENT)
+    else {
+        return vk::Result::ERROR_OUT_OF_HOST_MEMORY;
+    };
+    let data = std::alloc::alloc_zeroed(layout);
+    if data.is_null() {
+        return vk::Result::ERROR_OUT_OF_HOST_MEMORY;
+    }
+
+    objects.heap_usage[heap_index] += info.allocation_size;
+    let handle = objects.next_handle();
+    objects.memory.insert(
+        handle,
+        Memory {
+            data,
+            layout,
+            size: info.allocation_size,
+            memory_type_index: info.memory_type_index,
+            mapped: false,
+        },
+    );
+    *p_memory = vk::DeviceMemory::from_raw(handle);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn free_memory(
+    device: vk::Device,
+    memory: vk::DeviceMemory,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+) {
+    let state = State::from_handle(device);
+    let mut objects = state.objects();
+    if let Some(memory) = objects.memory.remove(&memory.as_raw()) {
+        let heap_index =
+            state.memory_properties.memory_types[memory.memory_type_index as usize].heap_index;
+        objects.heap_usage[heap_index as usize] -= memory.size;
+        std::alloc::dealloc(memory.data, memory.layout);
+    }
+}
+
+unsafe extern "system" fn map_memory(
+    device: vk::Device,
+    memory: vk::DeviceMemory,
+    offset: vk::DeviceSize,
+    _size: vk::DeviceSize,
+    _flags: vk::MemoryMapFlags,
+    pp_data: *mut *mut c_void,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let mut objects = state.objects();
+    if objects.faults.fail_map {
+        return vk::Result::ERROR_MEMORY_MAP_FAILED;
+    }
+    let Some(memory) = objects.memory.get_mut(&memory.as_raw()) else {
+        return vk::Result::ERROR_MEMORY_MAP_FAILED;
+    };
+    let flags =
+        state.memory_properties.memory_types[memory.memory_type_index as usize].property_flags;
+    if memory.mapped || !flags.contains(vk::MemoryPropertyFlags::HOST_VISIBLE) {
+        return vk::Result::ERROR_MEMORY_MAP_FAILED;
+    }
+
+    memory.mapped = true;
+    *pp_data = memory.data.add(offset as usize) as *mut c_void;
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn unmap_memory(device: vk::Device, memory: vk::DeviceMemory) {
+    let state = State::from_handle(device);
+    if let Some(memory) = state.objects().memory.get_mut(&memory.as_raw()) {
+        memory.mapped = false;
+    }
+}
+
+/// Host memory is always coherent, so flushing only records the ranges
+unsafe extern "system" fn flush_mapped_memory_ranges(
+    device: vk::Device,
+    memory_range_count: u32,
+    p_memory_ranges: *const vk::MappedMemoryRange<'_>,
+) -> vk::Result {
+    let ranges = std::slice::from_raw_parts(p_memory_ranges, memory_range_count as usize);
+    let mut objects = State::from_handle(device).objects();
+    let ranges = objects.mapped_ranges(ranges);
+    objects.flushed_ranges.extend(ranges);
+    vk::Result::SUCCESS
+}
+
+/// Host memory is always coherent, so invalidating only records the ranges
+unsafe extern "system" fn invalidate_mapped_memory_ranges(
+    device: vk::Device,
+    memory_range_count: u32,
+    p_memory_ranges: *const vk::MappedMemoryRange<'_>,
+) -> vk::Result {
+    let ranges = std::slice::from_raw_parts(p_memory_ranges, memory_range_count as usize);
+    let mut objects = State::from_handle(device).objects();
+    let ranges = objects.mapped_ranges(ranges);
+    objects.invalidated_ranges.extend(ranges);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn bind_buffer_memory(
+    device: vk::Device,
+    buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    memory_offset: vk::DeviceSize,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let mut objects = state.objects();
+    if !objects.memory.contains_key(&memory.as_raw()) {
+        return vk::Result::ERROR_UNKNOWN;
+    }
+    match objects.buffers.get_mut(&buffer.as_raw()) {
+        Some(buffer) => {
+            buffer.binding = Some((memory, memory_offset));
+            vk::Result::SUCCESS
+        }
+        None => vk::Result::ERROR_UNKNOWN,
+    }
+}
+
+unsafe extern "system" fn bind_image_memory(
+    device: vk::Device,
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    memory_offset: vk::DeviceSize,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let mut objects = state.objects();
+    if !objects.memory.contains_key(&memory.as_raw()) {
+        return vk::Result::ERROR_UNKNOWN;
+    }
+    match objects.images.get_mut(&image.as_raw()) {
+        Some(image) => {
+            image.binding = Some((memory, memory_offset));
+            vk::Result::SUCCESS
+        }
+        None => vk::Result::ERROR_UNKNOWN,
+    }
+}
+
+unsafe extern "system" fn bind_buffer_memory_2(
+    device: vk::Device,
+    bind_info_count: u32,
+    p_bind_infos: *const vk::BindBufferMemoryInfo<'_>,
+) -> vk::Result {
+    for info in std::slice::from_raw_parts(p_bind_infos, bind_info_count as usize) {
+        let result = bind_buffer_memory(device, info.buffer, info.memory, info.memory_offset);
+        if result != vk::Result::SUCCESS {
+            return result;
+        }
+    }
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn bind_image_memory_2(
+    device: vk::Device,
+    bind_info_count: u32,
+    p_bind_infos: *const vk::BindImageMemoryInfo<'_>,
+) -> vk::Result {
+    for info in std::slice::from_raw_parts(p_bind_infos, bind_info_count as usize) {
+        let result = bind_image_memory(device, info.image, info.memory, info.memory_offset);
+        if result != vk::Result::SUCCESS {
+            return result;
+        }
+    }
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn get_buffer_memory_requirements(
+    device: vk::Device,
+    buffer: vk::Buffer,
+    p_memory_requirements: *mut vk::MemoryRequirements,
+) {
+    let state = State::from_handle(device);
+    if let Some(buffer) = state.objects().buffers.get(&buffer.as_raw()) {
+        *p_memory_requirements = buffer.requirements;
+    }
+}
+
+unsafe extern "system" fn get_image_memory_requirements(
+    device: vk::Device,
+    image: vk::Image,
+    p_memory_requirements: *mut vk::MemoryRequirements,
+) {
+    let state = State::from_handle(device);
+    if let Some(image) = state.objects().images.get(&image.as_raw()) {
+        *p_memory_requirements = image.requirements;
+    }
+}
+
+unsafe extern "system" fn get_buffer_memory_requirements_2(
+    device: vk::Device,
+    p_info: *const vk::BufferMemoryRequirementsInfo2<'_>,
+    p_memory_requirements: *mut vk::MemoryRequirements2<'_>,
+) {
+    get_buffer_memory_requirements(
+        device,
+        (*p_info).buffer,
+        &mut (*p_memory_requirements).memory_requirements,
+    );
+}
+
+unsafe extern "system" fn get_image_memory_requirements_2(
+    device: vk::Device,
+    p_info: *const vk::ImageMemoryRequirementsInfo2<'_>,
+    p_memory_requirements: *mut vk::MemoryRequirements2<'_>,
+) {
+    get_image_memory_requirements(
+        device,
+        (*p_info).image,
+        &mut (*p_memory_requirements).memory_requirements,
+    );
+}
+
+unsafe extern "system" fn get_device_buffer_memory_requirements(
+    device: vk::Device,
+    p_info: *const vk::DeviceBufferMemoryRequirements<'_>,
+    p_memory_requirements: *mut vk::MemoryRequirements2<'_>,
+) {
+    let state = State::from_handle(device);
+    (*p_memory_requirements).memory_requirements =
+        state.buffer_requirements(&*(*p_info).p_create_info);
+}
+
+unsafe extern "system" fn get_device_image_memory_requirements(
+    device: vk::Device,
+    p_info: *const vk::DeviceImageMemoryRequirements<'_>,
+    p_memory_requirements: *mut vk::MemoryRequirements2<'_>,
+) {
+    let state = State::from_handle(device);
+    (*p_memory_requirements).memory_requirements =
+        state.image_requirements(&*(*p_info).p_create_info);
+}
+
+unsafe extern "system" fn create_buffer(
+    device: vk::Device,
+    p_create_info: *const vk::BufferCreateInfo<'_>,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+    p_buffer: *mut vk::Buffer,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let requirements = state.buffer_requirements(&*p_create_info);
+
+    let mut objects = state.objects();
+    let handle = objects.next_handle();
+    objects.buffers.insert(
+        handle,
+        Resource {
+            requirements,
+            binding: None,
+        },
+    );
+    *p_buffer = vk::Buffer::from_raw(handle);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_buffer(
+    device: vk::Device,
+    buffer: vk::Buffer,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+) {
+    let state = State::from_handle(device);
+    state.objects().buffers.remove(&buffer.as_raw());
+}
+
+unsafe extern "system" fn create_image(
+    device: vk::Device,
+    p_create_info: *const vk::ImageCreateInfo<'_>,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+    p_image: *mut vk::Image,
+) -> vk::Result {
+    let state = State::from_handle(device);
+    let requirements = state.image_requirements(&*p_create_info);
+
+    let mut objects = state.objects();
+    let handle = objects.next_handle();
+    objects.images.insert(
+        handle,
+        Resource {
+            requirements,
+            binding: None,
+        },
+    );
+    *p_image = vk::Image::from_raw(handle);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_image(
+    device: vk::Device,
+    image: vk::Image,
+    _p_allocator: *const vk::AllocationCallbacks<'_>,
+) {
+    let state = State::from_handle(device);
+    state.objects().images.remove(&image.as_raw());
+}
+
+/// Copies between the memory bound to both buffers right away.
+///
+/// Regions that do not fit into either buffer or its memory are skipped.
+unsafe extern "system" fn cmd_copy_buffer(
+    command_buffer: vk::CommandBuffer,
+    src_buffer: vk::Buffer,
+    dst_buffer: vk::Buffer,
+    region_count: u32,
+    p_regions: *const vk::BufferCopy,
+) {
+    let state = State::from_handle(command_buffer);
+    let objects = state.objects();
+    for region in std::slice::from_raw_parts(p_regions, region_count as usize) {
+        let src = objects.buffer_range(src_buffer, region.src_offset, region.size);
+        let dst = objects.buffer_range(dst_buffer, region.dst_offset, region.size);
+        if let (Some(src), Some(dst)) = (src, dst) {
+            std::ptr::copy(src, dst, region.size as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ash::vk;
+
+    use super::FakeDevice;
+    use crate::testing::MemoryTopology;
+    use crate::{vma, AllocatedBuffer, AllocationOptions, Allocator};
+
+    fn host_visible_device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .memory_type(
+                    0,
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL
+                        | vk::MemoryPropertyFlags::HOST_VISIBLE
+                        | vk::MemoryPropertyFlags::HOST_COHERENT,
+                ),
+        )
+    }
+
+    fn host_buffer(allocator: &Allocator, size: vk::DeviceSize) -> AllocatedBuffer {
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC | vk::BufferUsageFlags::TRANSFER_DST);
+        let options = AllocationOptions::new()
+            .usage(vma::MemoryUsage::AUTO)
+            .flags(vma::AllocationCreateFlags::HOST_ACCESS_RANDOM);
+        AllocatedBuffer::create(allocator, &buffer_info, &options).unwrap()
+    }
+
+    #[test]
+    fn copy_buffer_skips_out_of_bounds_regions() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut src = host_buffer(&allocator, 256);
+        let mut dst = host_buffer(&allocator, 256);
+        src.map().unwrap().fill(0xab);
+
+        let (src_buffer, dst_buffer) = (src.buffer(), dst.buffer());
+        let copy = |region: vk::BufferCopy| unsafe {
+            allocator.device().cmd_copy_buffer(
+                device.command_buffer(),
+                src_buffer,
+                dst_buffer,
+                &[region],
+            )
+        };
+        copy(vk::BufferCopy {
+            src_offset: 0,
+            dst_offset: 128,
+            size: 1 << 20,
+        });
+        copy(vk::BufferCopy {
+            src_offset: u64::MAX,
+            dst_offset: 0,
+            size: 16,
+        });
+        assert!(dst.map().unwrap().iter().all(|&byte| byte == 0));
+
+        copy(vk::BufferCopy {
+            src_offset: 0,
+            dst_offset: 128,
+            size: 128,
+        });
+        let data = dst.map().unwrap();
+        assert!(data[..128].iter().all(|&byte| byte == 0));
+        assert!(data[128..256].iter().all(|&byte| byte == 0xab));
+    }
+
+    #[test]
+    fn create_buffer_reports_failed_allocations() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        device.fail_allocations_after(0);
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(1024)
+            .usage(vk::BufferUsageFlags::VERTEX_BUFFER);
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::AUTO);
+        let error = AllocatedBuffer::create(&allocator, &buffer_info, &options).unwrap_err();
+
+        assert_eq!(error.function(), "vmaCreateBuffer");
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(device.memory_object_count(), 0);
+        assert_eq!(device.buffer_count(), 0);
+
+        device.clear_faults();
+        AllocatedBuffer::create(&allocator, &buffer_info, &options).unwrap();
+    }
+
+    #[test]
+    fn fail_nth_allocation_fails_once() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        device.fail_nth_allocation(1);
+        let requirements = vk::MemoryRequirements {
+            size: 1024,
+            alignment: 256,
+            memory_type_bits: 1,
+        };
+        let options = AllocationOptions::new().flags(vma::AllocationCreateFlags::DEDICATED_MEMORY);
+        let calls = device.allocation_calls();
+
+        // VMA does not retry dedicated allocations, so the first one fails and the second succeeds
+        let error = allocator.allocate(&requirements, &options).unwrap_err();
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        let (allocation, _) = allocator.allocate(&requirements, &options).unwrap();
+        assert!(device.allocation_calls() > calls + 1);
+
+        unsafe { allocator.free_memory(allocation) };
+        assert_eq!(device.memory_object_count(), 0);
+    }
+
+    #[test]
+    fn limit_heap_rejects_allocations_over_the_limit() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        device.limit_heap(0, 1 << 20);
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(2 << 20)
+            .usage(vk::BufferUsageFlags::VERTEX_BUFFER);
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::AUTO);
+        let error = AllocatedBuffer::create(&allocator, &buffer_info, &options).unwrap_err();
+
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert!(device.heap_usage(0) <= 1 << 20);
+    }
+
+    #[test]
+    fn allocate_memory_pages_rolls_back_partial_pages() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        // dedicated memory makes every page a separate `vkAllocateMemory` call
+        let requirements = [vk::MemoryRequirements {
+            size: 1024,
+            alignment: 256,
+            memory_type_bits: 1,
+        }; 3];
+        let create_infos = [vma::AllocationCreateInfo::default()
+            .flags(vma::AllocationCreateFlags::DEDICATED_MEMORY); 3];
+
+        device.fail_allocations_after(2);
+        let error =
+            unsafe { allocator.allocate_memory_pages(&requirements, &create_infos) }.unwrap_err();
+
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert!(device.allocation_calls() >= 3);
+        assert_eq!(device.memory_object_count(), 0);
+        assert_eq!(device.heap_usage(0), 0);
+    }
+
+    #[test]
+    fn create_pool_reports_failed_allocations() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        device.fail_allocations_after(0);
+        let pool_info = vma::PoolCreateInfo::default()
+            .memory_type_index(0)
+            .min_block_count(1);
+        let error = unsafe { allocator.create_pool(&pool_info) }.unwrap_err();
+
+        assert_eq!(error.function(), "vmaCreatePool");
+        assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(error.memory_type_index(), Some(0));
+        assert_eq!(device.memory_object_count(), 0);
+    }
+
+    #[test]
+    fn fail_map_memory_fails_mapping() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = host_buffer(&allocator, 256);
+
+        device.fail_map_memory(true);
+        let error = buffer.map().unwrap_err();
+        assert_eq!(error.result(), vk::Result::ERROR_MEMORY_MAP_FAILED);
+
+        device.fail_map_memory(false);
+        buffer.map().unwrap();
+    }
+
+    #[test]
+    fn fail_allocations_after_saturates() {
+        let device = host_visible_device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+
+        let requirements = vk::MemoryRequirements {
+            size: 1024,
+            alignment: 256,
+            memory_type_bits: 1,
+        };
+        let options = AllocationOptions::new().flags(vma::AllocationCreateFlags::DEDICATED_MEMORY);
+
+        // `u64::MAX + 1` calls until the failure must not wrap around to failing right away
+        device.fail_allocations_after(u64::MAX);
+        let (allocation, _) = allocator.allocate(&requirements, &options).unwrap();
+        unsafe { allocator.free_memory(allocation) };
+
+        // once failing, the countdown stays at the failing call instead of wrapping
+        device.fail_allocations_after(0);
+        let calls = device.allocation_calls();
+        for _ in 0..3 {
+            let error = allocator.allocate(&requirements, &options).unwrap_err();
+            assert_eq!(error.result(), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        }
+        assert!(device.allocation_calls() >= calls + 3);
+        assert_eq!(device.memory_object_count(), 0);
+    }
+
+    #[test]
+    fn allocate_memory_rejects_overflowing_sizes() {
+        let device = host_visible_device();
+        let vk_device = device.device();
+        let allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(1024)
+            .memory_type_index(0);
+        let memory = unsafe { vk_device.allocate_memory(&allocate_info, None) }.unwrap();
+
+        // 1024 + u64::MAX overflows the usage of the heap
+        let error =
+            unsafe { vk_device.allocate_memory(&allocate_info.allocation_size(u64::MAX), None) }
+                .unwrap_err();
+        assert_eq!(error, vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(device.heap_usage(0), 1024);
+
+        unsafe { vk_device.free_memory(memory, None) };
+        assert_eq!(device.heap_usage(0), 0);
+    }
+}
@@ -0,0 +1,34 @@
+//! Software stand-in for a Vulkan device, allowing an allocator to run without a GPU.
+//!
+//! ```rust ignore
+//! use ash::vk;
+//! use ash_mem_alloc::testing::{FakeDevice, MemoryTopology};
+//! use ash_mem_alloc::vma;
+//!
+//! let topology = MemoryTopology::new()
+//!     .heap(256 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+//!     .heap(256 << 20, vk::MemoryHeapFlags::empty())
+//!     .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL)
+//!     .memory_type(1, vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT);
+//!
+//! let device = FakeDevice::new(&topology);
+//! let allocator = device.create_allocator(&vma::AllocatorCreateInfo::default())?;
+//! ```
+//!
+//! [`MemoryTopology`] also comes with presets for common classes of hardware,
+//! e.g. [`MemoryTopology::discrete_small_bar`] or [`MemoryTopology::mobile`]:
+//!
+//! ```rust ignore
+//! for (name, topology) in MemoryTopology::presets() {
+//!     let allocator = FakeDevice::new(&topology).create_allocator(&vma::AllocatorCreateInfo::default())?;
+//!     let memory_type = allocator.find_memory_type(u32::MAX, &options)?;
+//!     println!("{name}: {memory_type}");
+//! }
+//! ```
+
+mod device;
+mod presets;
+mod topology;
+
+pub use device::FakeDevice;
+pub use topology::MemoryTopology;
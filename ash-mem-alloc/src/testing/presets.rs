@@ -0,0 +1,286 @@
+use ash::vk;
+
+use super::MemoryTopology;
+
+const MIB: vk::DeviceSize = 1024 * 1024;
+const GIB: vk::DeviceSize = 1024 * MIB;
+
+const DEVICE_LOCAL: vk::MemoryPropertyFlags = vk::MemoryPropertyFlags::DEVICE_LOCAL;
+const HOST_VISIBLE_COHERENT: vk::MemoryPropertyFlags = vk::MemoryPropertyFlags::from_raw(
+    vk::MemoryPropertyFlags::HOST_VISIBLE.as_raw()
+        | vk::MemoryPropertyFlags::HOST_COHERENT.as_raw(),
+);
+const HOST_CACHED_COHERENT: vk::MemoryPropertyFlags = vk::MemoryPropertyFlags::from_raw(
+    HOST_VISIBLE_COHERENT.as_raw() | vk::MemoryPropertyFlags::HOST_CACHED.as_raw(),
+);
+const AMD_COHERENT: vk::MemoryPropertyFlags = vk::MemoryPropertyFlags::from_raw(
+    vk::MemoryPropertyFlags::DEVICE_COHERENT_AMD.as_raw()
+        | vk::MemoryPropertyFlags::DEVICE_UNCACHED_AMD.as_raw(),
+);
+
+/// Ready-made topologies modelled after common classes of hardware
+impl MemoryTopology {
+    /// A discrete GPU without resizable BAR.
+    ///
+    /// Only a 256 MiB window of video memory is host visible, the rest is only reachable from the device.
+    pub fn discrete_small_bar() -> Self {
+        Self::new()
+            .heap(8 * GIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .heap(16 * GIB, vk::MemoryHeapFlags::empty())
+            .heap(256 * MIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL)
+            .memory_type(1, HOST_VISIBLE_COHERENT)
+            .memory_type(1, HOST_CACHED_COHERENT)
+            .memory_type(2, DEVICE_LOCAL | HOST_VISIBLE_COHERENT)
+    }
+
+    /// A discrete GPU with resizable BAR, all of video memory is host visible
+    pub fn discrete_rebar() -> Self {
+        Self::new()
+            .heap(8 * GIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .heap(16 * GIB, vk::MemoryHeapFlags::empty())
+            .memory_type(0, DEVICE_LOCAL)
+            .memory_type(1, HOST_VISIBLE_COHERENT)
+            .memory_type(1, HOST_CACHED_COHERENT)
+            .memory_type(0, DEVICE_LOCAL | HOST_VISIBLE_COHERENT)
+    }
+
+    /// An integrated GPU sharing a single heap of system memory with the host
+    pub fn integrated() -> Self {
+        Self::new()
+            .heap(16 * GIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL | HOST_VISIBLE_COHERENT)
+            .memory_type(0, DEVICE_LOCAL | HOST_CACHED_COHERENT)
+    }
+
+    /// A tile-based mobile GPU offering `LAZILY_ALLOCATED` memory for transient attachments
+    pub fn mobile() -> Self {
+        Self::new()
+            .heap(4 * GIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL | HOST_VISIBLE_COHERENT)
+            .memory_type(0, DEVICE_LOCAL | HOST_CACHED_COHERENT)
+            .memory_type(0, DEVICE_LOCAL | vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+    }
+
+    /// A discrete AMD GPU exposing `VK_AMD_device_coherent_memory`.
+    ///
+    /// Every regular memory type has a `DEVICE_COHERENT_AMD | DEVICE_UNCACHED_AMD` counterpart.
+    pub fn amd_device_coherent() -> Self {
+        Self::new()
+            .heap(8 * GIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .heap(16 * GIB, vk::MemoryHeapFlags::empty())
+            .heap(256 * MIB, vk::MemoryHeapFlags::DEVICE_LOCAL)
+            .memory_type(0, DEVICE_LOCAL)
+            .memory_type(1, HOST_VISIBLE_COHERENT)
+            .memory_type(2, DEVICE_LOCAL | HOST_VISIBLE_COHERENT)
+            .memory_type(1, HOST_CACHED_COHERENT)
+            .memory_type(0, DEVICE_LOCAL | AMD_COHERENT)
+            .memory_type(1, HOST_VISIBLE_COHERENT | AMD_COHERENT)
+            .memory_type(2, DEVICE_LOCAL | HOST_VISIBLE_COHERENT | AMD_COHERENT)
+    }
+
+    /// Returns every preset together with its name, for running a test against each class of hardware
+    pub fn presets() -> [(&'static str, Self); 5] {
+        [
+            ("discrete_small_bar", Self::discrete_small_bar()),
+            ("discrete_rebar", Self::discrete_rebar()),
+            ("integrated", Self::integrated()),
+            ("mobile", Self::mobile()),
+            ("amd_device_coherent", Self::amd_device_coherent()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ash::vk;
+
+    use super::*;
+    use crate::testing::FakeDevice;
+    use crate::{vma, AllocatedImage, AllocationOptions};
+
+    /// A common way of using a buffer
+    struct Case {
+        name: &'static str,
+        buffer_usage: vk::BufferUsageFlags,
+        memory_usage: vma::MemoryUsage,
+        flags: vma::AllocationCreateFlags,
+    }
+
+    fn cases() -> [Case; 5] {
+        [
+            Case {
+                name: "device only",
+                buffer_usage: vk::BufferUsageFlags::VERTEX_BUFFER,
+                memory_usage: vma::MemoryUsage::AUTO,
+                flags: vma::AllocationCreateFlags::empty(),
+            },
+            Case {
+                name: "staging upload",
+                buffer_usage: vk::BufferUsageFlags::TRANSFER_SRC,
+                memory_usage: vma::MemoryUsage::AUTO,
+                flags: vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE,
+            },
+            Case {
+                name: "dynamic uniforms",
+                buffer_usage: vk::BufferUsageFlags::UNIFORM_BUFFER,
+                memory_usage: vma::MemoryUsage::AUTO,
+                flags: vma::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE,
+            },
+            Case {
+                name: "readback",
+                buffer_usage: vk::BufferUsageFlags::TRANSFER_DST,
+                memory_usage: vma::MemoryUsage::AUTO,
+                flags: vma::AllocationCreateFlags::HOST_ACCESS_RANDOM,
+            },
+            Case {
+                name: "prefer host",
+                buffer_usage: vk::BufferUsageFlags::VERTEX_BUFFER,
+                memory_usage: vma::MemoryUsage::AUTO_PREFER_HOST,
+                flags: vma::AllocationCreateFlags::empty(),
+            },
+        ]
+    }
+
+    /// Property flags of the memory type VMA picks for each of the [`cases`] on `preset`
+    fn expected(preset: &str) -> [vk::MemoryPropertyFlags; 5] {
+        match preset {
+            "discrete_small_bar" | "discrete_rebar" | "amd_device_coherent" => [
+                DEVICE_LOCAL,
+                HOST_VISIBLE_COHERENT,
+                DEVICE_LOCAL | HOST_VISIBLE_COHERENT,
+                HOST_CACHED_COHERENT,
+                HOST_VISIBLE_COHERENT,
+            ],
+            // a single heap, so host visible memory is device local as well
+            "integrated" | "mobile" => [
+                DEVICE_LOCAL,
+                DEVICE_LOCAL | HOST_VISIBLE_COHERENT,
+                DEVICE_LOCAL | HOST_VISIBLE_COHERENT,
+                DEVICE_LOCAL | HOST_CACHED_COHERENT,
+                DEVICE_LOCAL,
+            ],
+            _ => panic!("no expectations for preset {preset}"),
+        }
+    }
+
+    #[test]
+    fn auto_usage_picks_expected_memory_types() {
+        for (preset, topology) in MemoryTopology::presets() {
+            let allocator = FakeDevice::new(&topology)
+                .create_allocator(&vma::AllocatorCreateInfo::default())
+                .unwrap();
+
+            for (case, expected) in cases().into_iter().zip(expected(preset)) {
+                let buffer_info = vk::BufferCreateInfo::default()
+                    .size(64 * 1024)
+                    .usage(case.buffer_usage);
+                let options = AllocationOptions::new()
+                    .usage(case.memory_usage)
+                    .flags(case.flags);
+                let memory_type = allocator
+                    .find_memory_type_for_buffer(&buffer_info, &options)
+                    .unwrap();
+
+                assert_eq!(
+                    allocator.get_memory_type_properties(memory_type),
+                    expected,
+                    "{preset}: {}",
+                    case.name
+                );
+            }
+        }
+    }
+
+    fn transient_attachment() -> vk::ImageCreateInfo<'static> {
+        vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(vk::Format::R8G8B8A8_UNORM)
+            .extent(vk::Extent3D {
+                width: 256,
+                height: 256,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .usage(
+                vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+            )
+    }
+
+    #[test]
+    fn transient_attachments_use_lazily_allocated_memory() {
+        let device = FakeDevice::new(&MemoryTopology::mobile());
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let options = AllocationOptions::new().usage(vma::MemoryUsage::GPU_LAZILY_ALLOCATED);
+
+        let image = AllocatedImage::create(&allocator, &transient_attachment(), &options).unwrap();
+        assert_eq!(
+            allocator.get_memory_type_properties(image.memory_type()),
+            DEVICE_LOCAL | vk::MemoryPropertyFlags::LAZILY_ALLOCATED
+        );
+        assert_eq!(device.memory_object_count(), 1);
+
+        // without lazily allocated memory the allocation fails instead of falling back
+        let allocator = FakeDevice::new(&MemoryTopology::discrete_rebar())
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let error = allocator
+            .find_memory_type_for_image(&transient_attachment(), &options)
+            .unwrap_err();
+        assert_eq!(error.result(), vk::Result::ERROR_FEATURE_NOT_PRESENT);
+    }
+
+    #[test]
+    fn amd_device_coherent_memory_needs_the_allocator_flag() {
+        let options =
+            AllocationOptions::new().required_flags(vk::MemoryPropertyFlags::DEVICE_COHERENT_AMD);
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(64 * 1024)
+            .usage(vk::BufferUsageFlags::STORAGE_BUFFER);
+
+        // VMA ignores the AMD memory types unless the allocator is told they may be used
+        let allocator = FakeDevice::new(&MemoryTopology::amd_device_coherent())
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let error = allocator
+            .find_memory_type_for_buffer(&buffer_info, &options)
+            .unwrap_err();
+        assert_eq!(error.result(), vk::Result::ERROR_FEATURE_NOT_PRESENT);
+
+        let create_info = vma::AllocatorCreateInfo::default()
+            .flags(vma::AllocatorCreateFlags::AMD_DEVICE_COHERENT_MEMORY);
+        let allocator = FakeDevice::new(&MemoryTopology::amd_device_coherent())
+            .create_allocator(&create_info)
+            .unwrap();
+        let memory_type = allocator
+            .find_memory_type_for_buffer(&buffer_info, &options)
+            .unwrap();
+        assert_eq!(
+            allocator.get_memory_type_properties(memory_type),
+            DEVICE_LOCAL | AMD_COHERENT
+        );
+
+        // regular allocations still avoid the uncached types
+        for (case, expected) in cases().into_iter().zip(expected("amd_device_coherent")) {
+            let buffer_info = buffer_info.usage(case.buffer_usage);
+            let options = AllocationOptions::new()
+                .usage(case.memory_usage)
+                .flags(case.flags);
+            let memory_type = allocator
+                .find_memory_type_for_buffer(&buffer_info, &options)
+                .unwrap();
+            assert_eq!(
+                allocator.get_memory_type_properties(memory_type),
+                expected,
+                "{}",
+                case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use ash::vk;
+
+/// Describes the memory heaps and memory types reported by a [`FakeDevice`](super::FakeDevice)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTopology {
+    heaps: Vec<vk::MemoryHeap>,
+    types: Vec<vk::MemoryType>,
+}
+
+impl MemoryTopology {
+    /// Creates a topology without any heaps or memory types
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a heap of `size` bytes, it gets the next free heap index
+    pub fn heap(mut self, size: vk::DeviceSize, flags: vk::MemoryHeapFlags) -> Self {
+        assert!(
+            self.heaps.len() < vk::MAX_MEMORY_HEAPS,
+            "a device can not have more than {} memory heaps",
+            vk::MAX_MEMORY_HEAPS
+        );
+        self.heaps.push(vk::MemoryHeap { size, flags });
+        self
+    }
+
+    /// Adds a memory type living in heap `heap_index`, it gets the next free memory type index
+    pub fn memory_type(mut self, heap_index: u32, flags: vk::MemoryPropertyFlags) -> Self {
+        assert!(
+            (heap_index as usize) < self.heaps.len(),
+            "memory type refers to heap {heap_index}, but only {} heaps exist",
+            self.heaps.len()
+        );
+        assert!(
+            self.types.len() < vk::MAX_MEMORY_TYPES,
+            "a device can not have more than {} memory types",
+            vk::MAX_MEMORY_TYPES
+        );
+        self.types.push(vk::MemoryType {
+            property_flags: flags,
+            heap_index,
+        });
+        self
+    }
+
+    /// Returns the heaps of this topology
+    pub fn heaps(&self) -> &[vk::MemoryHeap] {
+        &self.heaps
+    }
+
+    /// Returns the memory types of this topology
+    pub fn memory_types(&self) -> &[vk::MemoryType] {
+        &self.types
+    }
+
+    /// Builds the memory properties reported by `vkGetPhysicalDeviceMemoryProperties`
+    pub fn memory_properties(&self) -> vk::PhysicalDeviceMemoryProperties {
+        let mut properties = vk::PhysicalDeviceMemoryProperties {
+            memory_heap_count: self.heaps.len() as u32,
+            memory_type_count: self.types.len() as u32,
+            ..Default::default()
+        };
+        properties.memory_heaps[..self.heaps.len()].copy_from_slice(&self.heaps);
+        properties.memory_types[..self.types.len()].copy_from_slice(&self.types);
+        properties
+    }
+}
@@ -0,0 +1,304 @@
+use ash::vk;
+use bytemuck::Pod;
+
+use crate::{vma, AllocatedBuffer, Allocator};
+
+/// Error returned by the typed transfer helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferError {
+    /// The requested range does not fit into the allocation
+    OutOfBounds {
+        /// offset of the requested range inside of the allocation
+        offset: vk::DeviceSize,
+        /// size of the requested range in bytes
+        size: vk::DeviceSize,
+        /// size of the allocation in bytes
+        allocation_size: vk::DeviceSize,
+    },
+    /// The allocation does not live in a `HOST_VISIBLE` memory type
+    NotHostVisible {
+        /// property flags of the memory type of the allocation
+        memory_properties: vk::MemoryPropertyFlags,
+    },
+    /// VMA failed to perform the copy
+    Vma(vma::Error),
+}
+
+impl std::fmt::Display for TransferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds {
+                offset,
+                size,
+                allocation_size,
+            } => write!(
+                f,
+                "range of {size} bytes at offset {offset} exceeds allocation of {allocation_size} bytes"
+            ),
+            Self::NotHostVisible { memory_properties } => write!(
+                f,
+                "allocation is not host visible, its memory type has {memory_properties:?}"
+            ),
+            Self::Vma(error) => std::fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for TransferError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::OutOfBounds { .. } | Self::NotHostVisible { .. } => None,
+            // the message of a VMA error is shown directly, so only its own source is reported
+            Self::Vma(error) => error.source(),
+        }
+    }
+}
+
+impl From<vma::Error> for TransferError {
+    fn from(error: vma::Error) -> Self {
+        Self::Vma(error)
+    }
+}
+
+impl Allocator {
+    /// Checks that `allocation` is host visible and that `size` bytes starting at `offset` lie inside of it.
+    unsafe fn check_transfer(
+        &self,
+        allocation: vma::Allocation,
+        offset: vk::DeviceSize,
+        size: vk::DeviceSize,
+    ) -> Result<(), TransferError> {
+        let memory_properties = self.get_allocation_memory_properties(allocation);
+        if !memory_properties.contains(vk::MemoryPropertyFlags::HOST_VISIBLE) {
+            return Err(TransferError::NotHostVisible { memory_properties });
+        }
+
+        let allocation_size = self.get_allocation_info(allocation).size;
+        match offset.checked_add(size) {
+            Some(end) if end <= allocation_size => Ok(()),
+            _ => Err(TransferError::OutOfBounds {
+                offset,
+                size,
+                allocation_size,
+            }),
+        }
+    }
+
+    /// Copies `data` into `allocation`, starting `offset` bytes into the allocation.
+    ///
+    /// The size of the copy is derived from `data` and checked against the size of the allocation.
+    /// Fails with [`TransferError::NotHostVisible`] if the allocation is not host visible.
+    ///
+    /// See [`vma::copy_memory_to_allocation`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn write_slice<T: Pod>(
+        &self,
+        allocation: vma::Allocation,
+        offset: vk::DeviceSize,
+        data: &[T],
+    ) -> Result<(), TransferError> {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let size = bytes.len() as vk::DeviceSize;
+        self.check_transfer(allocation, offset, size)?;
+        if size == 0 {
+            return Ok(());
+        }
+
+        self.copy_memory_to_allocation(bytes, allocation, offset, size)?;
+        Ok(())
+    }
+
+    /// Reads `count` elements of type `T` from `allocation`, starting `offset` bytes into the allocation.
+    ///
+    /// The size of the copy is checked against the size of the allocation.
+    /// Fails with [`TransferError::NotHostVisible`] if the allocation is not host visible.
+    ///
+    /// See [`vma::copy_allocation_to_memory`]
+    ///
+    /// # Safety
+    /// `allocation` must be a live allocation created by this allocator.
+    pub unsafe fn read_vec<T: Pod>(
+        &self,
+        allocation: vma::Allocation,
+        offset: vk::DeviceSize,
+        count: usize,
+    ) -> Result<Vec<T>, TransferError> {
+        let size = std::mem::size_of::<T>()
+            .checked_mul(count)
+            .map_or(vk::DeviceSize::MAX, |size| size as vk::DeviceSize);
+        self.check_transfer(allocation, offset, size)?;
+
+        let mut data = vec![T::zeroed(); count];
+        if size == 0 {
+            return Ok(data);
+        }
+
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+        self.copy_allocation_to_memory(allocation, offset, bytes, size)?;
+        Ok(data)
+    }
+}
+
+impl AllocatedBuffer {
+    /// Copies `data` into the memory of this buffer, starting `offset` bytes into the allocation.
+    ///
+    /// Fails with [`TransferError::NotHostVisible`] if the memory of the buffer is not host visible.
+    ///
+    /// See [`Allocator::write_slice`]
+    pub fn write_slice<T: Pod>(
+        &mut self,
+        offset: vk::DeviceSize,
+        data: &[T],
+    ) -> Result<(), TransferError> {
+        unsafe {
+            self.allocator()
+                .write_slice(self.allocation(), offset, data)
+        }
+    }
+
+    /// Reads `count` elements of type `T` from the memory of this buffer,
+    /// starting `offset` bytes into the allocation.
+    ///
+    /// Fails with [`TransferError::NotHostVisible`] if the memory of the buffer is not host visible.
+    ///
+    /// See [`Allocator::read_vec`]
+    pub fn read_vec<T: Pod>(
+        &self,
+        offset: vk::DeviceSize,
+        count: usize,
+    ) -> Result<Vec<T>, TransferError> {
+        unsafe { self.allocator().read_vec(self.allocation(), offset, count) }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use ash::vk;
+
+    use super::TransferError;
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::{vma, AllocatedBuffer, AllocationOptions, Allocator};
+
+    /// A device with a device local type and a host visible, non-coherent type
+    fn device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .heap(64 << 20, vk::MemoryHeapFlags::empty())
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL)
+                .memory_type(1, vk::MemoryPropertyFlags::HOST_VISIBLE),
+        )
+    }
+
+    fn buffer(allocator: &Allocator, required_flags: vk::MemoryPropertyFlags) -> AllocatedBuffer {
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(1024)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC);
+        let options = AllocationOptions::new().required_flags(required_flags);
+        AllocatedBuffer::create(allocator, &buffer_info, &options).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator, vk::MemoryPropertyFlags::HOST_VISIBLE);
+
+        buffer.write_slice(16, &[1u32, 2, 3, 4]).unwrap();
+        assert_eq!(buffer.read_vec::<u32>(16, 4).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(buffer.read_vec::<u16>(20, 3).unwrap(), [2, 0, 3]);
+        assert_eq!(buffer.read_vec::<u32>(0, 4).unwrap(), [0; 4]);
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator, vk::MemoryPropertyFlags::HOST_VISIBLE);
+        let allocation_size = buffer.size();
+
+        assert_eq!(
+            buffer.write_slice(allocation_size - 4, &[0u32; 2]),
+            Err(TransferError::OutOfBounds {
+                offset: allocation_size - 4,
+                size: 8,
+                allocation_size,
+            })
+        );
+        assert_eq!(
+            buffer.read_vec::<u64>(allocation_size, 1),
+            Err(TransferError::OutOfBounds {
+                offset: allocation_size,
+                size: 8,
+                allocation_size,
+            })
+        );
+        // the last element fits exactly
+        buffer.write_slice(allocation_size - 4, &[7u32]).unwrap();
+    }
+
+    #[test]
+    fn read_vec_rejects_overflowing_counts() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let buffer = buffer(&allocator, vk::MemoryPropertyFlags::HOST_VISIBLE);
+
+        // `usize::MAX` elements of 4 bytes overflow before anything is allocated
+        assert_eq!(
+            buffer.read_vec::<u32>(0, usize::MAX),
+            Err(TransferError::OutOfBounds {
+                offset: 0,
+                size: vk::DeviceSize::MAX,
+                allocation_size: buffer.size(),
+            })
+        );
+    }
+
+    #[test]
+    fn device_local_memory_is_rejected() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator, vk::MemoryPropertyFlags::DEVICE_LOCAL);
+        let error = TransferError::NotHostVisible {
+            memory_properties: vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        };
+
+        assert_eq!(buffer.write_slice(0, &[1u32]), Err(error));
+        assert_eq!(buffer.read_vec::<u32>(0, 1), Err(error));
+        // an empty transfer is still rejected
+        assert_eq!(buffer.write_slice::<u32>(0, &[]), Err(error));
+    }
+
+    #[test]
+    fn empty_transfers_do_not_reach_vma() {
+        let device = device();
+        let allocator = device
+            .create_allocator(&vma::AllocatorCreateInfo::default())
+            .unwrap();
+        let mut buffer = buffer(&allocator, vk::MemoryPropertyFlags::HOST_VISIBLE);
+        let allocation_size = buffer.size();
+
+        buffer.write_slice::<u32>(allocation_size, &[]).unwrap();
+        assert!(buffer
+            .read_vec::<u32>(allocation_size, 0)
+            .unwrap()
+            .is_empty());
+
+        // copies into non-coherent memory would have flushed or invalidated
+        assert!(device.take_flushed_ranges().is_empty());
+        assert!(device.take_invalidated_ranges().is_empty());
+
+        buffer.write_slice(0, &[1u32]).unwrap();
+        assert_eq!(device.take_flushed_ranges().len(), 1);
+    }
+}
@@ -1,6 +1,42 @@
 
 #define VMA_IMPLEMENTATION
+#ifdef VMA_RS_STATIC_VULKAN_FUNCTIONS
+#define VMA_STATIC_VULKAN_FUNCTIONS 1
+#define VMA_DYNAMIC_VULKAN_FUNCTIONS 0
+#else
 #define VK_NO_PROTOTYPES
 #define VMA_STATIC_VULKAN_FUNCTIONS 0
 #define VMA_DYNAMIC_VULKAN_FUNCTIONS 1
+#endif
+
+#ifdef VMA_RS_ASSERT_HOOK
+// implemented in src/assert.rs
+extern "C" void vma_rs_assert_failed(const char* expression, const char* file, int line);
+
+#define VMA_ASSERT(expr) do { if(!(expr)) { vma_rs_assert_failed(#expr, __FILE__, __LINE__); } } while(false)
+#ifdef VMA_RS_HEAVY_ASSERT
+#define VMA_HEAVY_ASSERT(expr) VMA_ASSERT(expr)
+#endif
+#endif
+
+#ifdef VMA_RS_DEBUG_LOG
+#include <cstdarg>
+#include <cstdio>
+
+// implemented in src/debug_log.rs
+extern "C" void vma_rs_debug_log(const char* message);
+
+static void vma_rs_debug_log_format(const char* format, ...)
+{
+    char message[1024];
+    va_list args;
+    va_start(args, format);
+    vsnprintf(message, sizeof(message), format, args);
+    va_end(args);
+    vma_rs_debug_log(message);
+}
+
+#define VMA_DEBUG_LOG_FORMAT(...) vma_rs_debug_log_format(__VA_ARGS__)
+#endif
+
 #include <vk_mem_alloc.h>
@@ -0,0 +1,323 @@
+use std::ffi::CStr;
+
+use ash::vk;
+
+use crate::vma;
+
+impl vma::VulkanFunctions {
+    /// Fills the function pointers from the function tables of `entry`, `instance` and `device`,
+    /// so VMA does not have to look up any function itself.
+    ///
+    /// The Vulkan 1.1 and 1.3 functions are only taken from the core tables if `api_version` provides them,
+    /// otherwise they stay `None`. The loaders fill unsupported functions with panicking stubs,
+    /// and VMA decides whether to call some of them just by checking them for null.
+    /// Use [`vma::VulkanFunctions::from_ash`] to fall back to the `KHR` extensions instead.
+    ///
+    /// `api_version` should be the same as [`vma::AllocatorCreateInfo::vulkan_api_version`].
+    pub fn from_loaders(
+        entry: &ash::Entry,
+        instance: &ash::Instance,
+        device: &ash::Device,
+        api_version: u32,
+    ) -> Self {
+        let instance_1_0 = instance.fp_v1_0();
+        let device_1_0 = device.fp_v1_0();
+
+        let mut functions = Self::default()
+            .get_instance_proc_addr(Some(entry.static_fn().get_instance_proc_addr))
+            .get_device_proc_addr(Some(instance_1_0.get_device_proc_addr))
+            .get_physical_device_properties(Some(instance_1_0.get_physical_device_properties))
+            .get_physical_device_memory_properties(Some(
+                instance_1_0.get_physical_device_memory_properties,
+            ))
+            .allocate_memory(Some(device_1_0.allocate_memory))
+            .free_memory(Some(device_1_0.free_memory))
+            .map_memory(Some(device_1_0.map_memory))
+            .unmap_memory(Some(device_1_0.unmap_memory))
+            .flush_mapped_memory_ranges(Some(device_1_0.flush_mapped_memory_ranges))
+            .invalidate_mapped_memory_ranges(Some(device_1_0.invalidate_mapped_memory_ranges))
+            .bind_buffer_memory(Some(device_1_0.bind_buffer_memory))
+            .bind_image_memory(Some(device_1_0.bind_image_memory))
+            .get_buffer_memory_requirements(Some(device_1_0.get_buffer_memory_requirements))
+            .get_image_memory_requirements(Some(device_1_0.get_image_memory_requirements))
+            .create_buffer(Some(device_1_0.create_buffer))
+            .destroy_buffer(Some(device_1_0.destroy_buffer))
+            .create_image(Some(device_1_0.create_image))
+            .destroy_image(Some(device_1_0.destroy_image))
+            .cmd_copy_buffer(Some(device_1_0.cmd_copy_buffer));
+
+        if has_version(api_version, 1, 1) {
+            let instance_1_1 = instance.fp_v1_1();
+            let device_1_1 = device.fp_v1_1();
+            functions = functions
+                .get_buffer_memory_requirements_2_khr(Some(
+                    device_1_1.get_buffer_memory_requirements2,
+                ))
+                .get_image_memory_requirements_2_khr(Some(
+                    device_1_1.get_image_memory_requirements2,
+                ))
+                .bind_buffer_memory_2_khr(Some(device_1_1.bind_buffer_memory2))
+                .bind_image_memory_2_khr(Some(device_1_1.bind_image_memory2))
+                .get_physical_device_memory_properties_2_khr(Some(
+                    instance_1_1.get_physical_device_memory_properties2,
+                ));
+        }
+
+        if has_version(api_version, 1, 3) {
+            let device_1_3 = device.fp_v1_3();
+            functions = functions
+                .get_device_buffer_memory_requirements(Some(
+                    device_1_3.get_device_buffer_memory_requirements,
+                ))
+                .get_device_image_memory_requirements(Some(
+                    device_1_3.get_device_image_memory_requirements,
+                ));
+        }
+
+        functions
+    }
+
+    /// Like [`vma::VulkanFunctions::from_loaders`], but functions that `api_version` does not provide
+    /// are taken from the `KHR` extensions in `enabled_extensions` where possible.
+    ///
+    /// `enabled_extensions` are the names of all instance and device extensions that were enabled.
+    /// Functions backed by neither the version nor an extension stay `None`:
+    ///
+    /// * the `*_memory_requirements_2` functions need Vulkan 1.1 or `VK_KHR_get_memory_requirements2`
+    /// * the `bind_*_memory_2` functions need Vulkan 1.1 or `VK_KHR_bind_memory2`
+    /// * `get_physical_device_memory_properties_2` needs Vulkan 1.1 or `VK_KHR_get_physical_device_properties2`
+    /// * the `get_device_*_memory_requirements` functions need Vulkan 1.3 or `VK_KHR_maintenance4`
+    ///
+    /// `api_version` should be the same as [`vma::AllocatorCreateInfo::vulkan_api_version`].
+    pub fn from_ash(
+        entry: &ash::Entry,
+        instance: &ash::Instance,
+        device: &ash::Device,
+        api_version: u32,
+        enabled_extensions: &[&CStr],
+    ) -> Self {
+        let has_extension = |name: &CStr| enabled_extensions.contains(&name);
+
+        let mut functions = Self::from_loaders(entry, instance, device, api_version);
+
+        if !has_version(api_version, 1, 1) {
+            if has_extension(ash::khr::get_memory_requirements2::NAME) {
+                let loader = ash::khr::get_memory_requirements2::Device::new(instance, device);
+                functions = functions
+                    .get_buffer_memory_requirements_2_khr(Some(
+                        loader.fp().get_buffer_memory_requirements2_khr,
+                    ))
+                    .get_image_memory_requirements_2_khr(Some(
+                        loader.fp().get_image_memory_requirements2_khr,
+                    ));
+            }
+
+            if has_extension(ash::khr::bind_memory2::NAME) {
+                let loader = ash::khr::bind_memory2::Device::new(instance, device);
+                functions = functions
+                    .bind_buffer_memory_2_khr(Some(loader.fp().bind_buffer_memory2_khr))
+                    .bind_image_memory_2_khr(Some(loader.fp().bind_image_memory2_khr));
+            }
+
+            if has_extension(ash::khr::get_physical_device_properties2::NAME) {
+                let loader =
+                    ash::khr::get_physical_device_properties2::Instance::new(entry, instance);
+                functions = functions.get_physical_device_memory_properties_2_khr(Some(
+                    loader.fp().get_physical_device_memory_properties2_khr,
+                ));
+            }
+        }
+
+        if !has_version(api_version, 1, 3) && has_extension(ash::khr::maintenance4::NAME) {
+            let loader = ash::khr::maintenance4::Device::new(instance, device);
+            functions = functions
+                .get_device_buffer_memory_requirements(Some(
+                    loader.fp().get_device_buffer_memory_requirements_khr,
+                ))
+                .get_device_image_memory_requirements(Some(
+                    loader.fp().get_device_image_memory_requirements_khr,
+                ));
+        }
+
+        functions
+    }
+}
+
+/// Checks whether `api_version` is at least `major.minor`
+fn has_version(api_version: u32, major: u32, minor: u32) -> bool {
+    (
+        vk::api_version_major(api_version),
+        vk::api_version_minor(api_version),
+    ) >= (major, minor)
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::ffi::CStr;
+
+    use ash::vk;
+
+    use crate::testing::{FakeDevice, MemoryTopology};
+    use crate::vma;
+
+    fn device() -> FakeDevice {
+        FakeDevice::new(
+            &MemoryTopology::new()
+                .heap(64 << 20, vk::MemoryHeapFlags::DEVICE_LOCAL)
+                .memory_type(0, vk::MemoryPropertyFlags::DEVICE_LOCAL),
+        )
+    }
+
+    fn from_loaders(device: &FakeDevice, api_version: u32) -> vma::VulkanFunctions {
+        vma::VulkanFunctions::from_loaders(
+            &device.entry(),
+            &device.instance(),
+            &device.device(),
+            api_version,
+        )
+    }
+
+    /// Which of the Vulkan 1.1 functions are set, i.e. the `*_memory_requirements_2`,
+    /// `bind_*_memory_2` and `get_physical_device_memory_properties_2` functions
+    fn has_1_1(functions: &vma::VulkanFunctions) -> [bool; 5] {
+        [
+            functions.vk_get_buffer_memory_requirements_2_khr.is_some(),
+            functions.vk_get_image_memory_requirements_2_khr.is_some(),
+            functions.vk_bind_buffer_memory_2_khr.is_some(),
+            functions.vk_bind_image_memory_2_khr.is_some(),
+            functions
+                .vk_get_physical_device_memory_properties_2_khr
+                .is_some(),
+        ]
+    }
+
+    /// Which of the Vulkan 1.3 `get_device_*_memory_requirements` functions are set
+    fn has_1_3(functions: &vma::VulkanFunctions) -> [bool; 2] {
+        [
+            functions.vk_get_device_buffer_memory_requirements.is_some(),
+            functions.vk_get_device_image_memory_requirements.is_some(),
+        ]
+    }
+
+    #[test]
+    fn from_loaders_fills_the_functions_of_the_api_version() {
+        let device = device();
+
+        let functions = from_loaders(&device, vk::API_VERSION_1_0);
+        assert!(functions.vk_get_instance_proc_addr.is_some());
+        assert!(functions.vk_get_device_proc_addr.is_some());
+        assert!(functions.vk_allocate_memory.is_some());
+        assert!(functions.vk_cmd_copy_buffer.is_some());
+        assert_eq!(has_1_1(&functions), [false; 5]);
+        assert_eq!(has_1_3(&functions), [false; 2]);
+
+        let functions = from_loaders(&device, vk::API_VERSION_1_1);
+        assert_eq!(has_1_1(&functions), [true; 5]);
+        assert_eq!(has_1_3(&functions), [false; 2]);
+
+        let functions = from_loaders(&device, vk::API_VERSION_1_2);
+        assert_eq!(has_1_1(&functions), [true; 5]);
+        assert_eq!(has_1_3(&functions), [false; 2]);
+
+        let functions = from_loaders(&device, vk::API_VERSION_1_3);
+        assert_eq!(has_1_1(&functions), [true; 5]);
+        assert_eq!(has_1_3(&functions), [true; 2]);
+    }
+
+    #[test]
+    fn from_loaders_creates_a_working_allocator() {
+        let device = device();
+        let functions = from_loaders(&device, vk::API_VERSION_1_3);
+        let create_info = vma::AllocatorCreateInfo::default()
+            .vulkan_api_version(vk::API_VERSION_1_3)
+            .vulkan_functions(&functions);
+        let allocator = unsafe {
+            crate::Allocator::new(
+                &device.instance(),
+                &device.device(),
+                device.physical_device(),
+                &create_info,
+            )
+        }
+        .unwrap();
+
+        let requirements = vk::MemoryRequirements {
+            size: 1024,
+            alignment: 256,
+            memory_type_bits: 1,
+        };
+        let (allocation, _) = allocator
+            .allocate(&requirements, &crate::AllocationOptions::new())
+            .unwrap();
+        assert_eq!(device.memory_object_count(), 1);
+        unsafe { allocator.free_memory(allocation) };
+    }
+
+    fn from_ash(
+        device: &FakeDevice,
+        api_version: u32,
+        enabled_extensions: &[&CStr],
+    ) -> vma::VulkanFunctions {
+        vma::VulkanFunctions::from_ash(
+            &device.entry(),
+            &device.instance(),
+            &device.device(),
+            api_version,
+            enabled_extensions,
+        )
+    }
+
+    #[test]
+    fn from_ash_leaves_functions_of_disabled_extensions_unset() {
+        let device = device();
+
+        let functions = from_ash(&device, vk::API_VERSION_1_0, &[]);
+        assert_eq!(has_1_1(&functions), [false; 5]);
+        assert_eq!(has_1_3(&functions), [false; 2]);
+
+        // the budget extension alone does not provide `vkGetPhysicalDeviceMemoryProperties2KHR`
+        let functions = from_ash(
+            &device,
+            vk::API_VERSION_1_0,
+            &[ash::ext::memory_budget::NAME],
+        );
+        assert_eq!(has_1_1(&functions), [false; 5]);
+    }
+
+    #[test]
+    fn from_ash_takes_functions_from_enabled_extensions() {
+        let device = device();
+
+        let functions = from_ash(
+            &device,
+            vk::API_VERSION_1_0,
+            &[
+                ash::ext::memory_budget::NAME,
+                ash::khr::get_physical_device_properties2::NAME,
+            ],
+        );
+        assert_eq!(has_1_1(&functions), [false, false, false, false, true]);
+
+        let functions = from_ash(
+            &device,
+            vk::API_VERSION_1_0,
+            &[ash::khr::bind_memory2::NAME],
+        );
+        assert_eq!(has_1_1(&functions), [false, false, true, true, false]);
+
+        let functions = from_ash(
+            &device,
+            vk::API_VERSION_1_0,
+            &[ash::khr::get_memory_requirements2::NAME],
+        );
+        assert_eq!(has_1_1(&functions), [true, true, false, false, false]);
+
+        let functions = from_ash(
+            &device,
+            vk::API_VERSION_1_1,
+            &[ash::khr::maintenance4::NAME],
+        );
+        assert_eq!(has_1_1(&functions), [true; 5]);
+        assert_eq!(has_1_3(&functions), [true; 2]);
+    }
+}
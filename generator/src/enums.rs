@@ -24,6 +24,17 @@ pub fn generate_enums(tu: &Entity) -> TokenStream {
     }
 }
 
+/// Returns the rust names of all Vma enums
+pub fn enum_names(tu: &Entity) -> Vec<String> {
+    tu.get_children()
+        .iter()
+        .filter(|item| {
+            item.get_kind() == EntityKind::EnumDecl && item.get_name().unwrap().starts_with("Vma")
+        })
+        .map(|item| parse_enum(item).name.to_string())
+        .collect()
+}
+
 /// Generates the rust code for a single parsed enum
 fn generate_enum(e: &VmaEnum) -> TokenStream {
     let name = &e.name;
@@ -100,9 +111,64 @@ fn generate_enum(e: &VmaEnum) -> TokenStream {
         }
     });
 
+    // names of all variants, used for formatting and (de)serialization
+    let names = e.variants.iter().map(|variant| {
+        let name = variant.name.to_string();
+        let val = &variant.value;
+        quote! { (#name, #val) }
+    });
+
+    // flags are formatted as their named bits joined by " | ", enums as the name of their variant
+    let fmt = if e.is_bitfield {
+        quote! { fmt_flags }
+    } else {
+        quote! { fmt_value }
+    };
+    let fmt_impls = quote! {
+        impl #name {
+            /// Names and values of all variants, in declaration order
+            const NAMES: &'static [(&'static str, u32)] = &[#(#names),*];
+        }
+
+        impl ::std::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                crate::names::#fmt(f, self.0, Self::NAMES)
+            }
+        }
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                crate::names::#fmt(f, self.0, Self::NAMES)
+            }
+        }
+    };
+
+    // optional name-based (de)serialization, flags are written as a list of names
+    let (serialize, deserialize) = if e.is_bitfield {
+        (quote! { serialize_flags }, quote! { deserialize_flags })
+    } else {
+        (quote! { serialize_value }, quote! { deserialize_value })
+    };
+    let type_name = name.to_string();
+    let serde_impls = quote! {
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for #name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                crate::names::#serialize(self.0, Self::NAMES, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                crate::names::#deserialize(#type_name, Self::NAMES, deserializer).map(Self)
+            }
+        }
+    };
+
     quote! {
         #[repr(transparent)]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         #docs
         pub struct #name(u32);
 
@@ -123,6 +189,10 @@ fn generate_enum(e: &VmaEnum) -> TokenStream {
         }
 
         #bit_impls
+
+        #fmt_impls
+
+        #serde_impls
     }
 }
 
@@ -6,6 +6,20 @@ use syn::{Ident, LitStr};
 
 use crate::parsing::{translate_ffi_type, translate_var, ArrayLen, VmaVarKind};
 
+/// Pointer parameters that vma reads and writes, given as (function, parameter).
+///
+/// The header does not distinguish these from pure output parameters,
+/// so they would otherwise be replaced by a zeroed temporary.
+const IN_OUT_PARAMS: &[(&str, &str)] = &[("vmaEndDefragmentationPass", "pPassInfo")];
+
+/// Functions that return `VK_INCOMPLETE` as a regular status instead of an error.
+const INCOMPLETE_IS_SUCCESS: &[&str] = &["vmaBeginDefragmentationPass", "vmaEndDefragmentationPass"];
+
+/// Hand-written types that replace the returned values of a function, given as (function, type).
+///
+/// The type is built through `From` with the tuple of values that would be returned otherwise.
+const RETURN_WRAPPERS: &[(&str, &str)] = &[("vmaBeginDefragmentationPass", "PassResult")];
+
 /// Generates a rust function for every vma function
 pub fn generate_functions(tu: &Entity) -> TokenStream {
     let mut vma_functions = Vec::new();
@@ -49,8 +63,10 @@ fn generate_func(func: VmaFunction) -> TokenStream {
                 Some(quote! { #name: Option<&#mutability impl #trait_name> })
             }
             VmaVarKind::Ref(ref_ty) => Some(quote! {#name: &#ref_ty}),
+            VmaVarKind::RefInOut(ref_ty) => Some(quote! {#name: &mut #ref_ty}),
             VmaVarKind::Array(array_ty, _) => Some(quote! {#name: &[#array_ty]}),
             VmaVarKind::Str => Some(quote! {#name: Option<&::std::ffi::CStr>}),
+            VmaVarKind::StrFree => Some(quote! {#name: *mut ::std::ffi::c_char}),
             VmaVarKind::BufferConst(_) => Some(quote! { #name: &[u8] }),
             VmaVarKind::BufferMut(_) => Some(quote! { #name: &mut [u8] }),
             _ => None,
@@ -63,20 +79,36 @@ fn generate_func(func: VmaFunction) -> TokenStream {
         quote! {#name: #ty}
     });
 
+    let lifetime = if func.args.iter().any(|arg| arg.needs_lifetime) {
+        quote!{ <'a> }
+    } else {
+        quote!{}
+    };
+
+    // look up a hand-written type to wrap the returned values in
+    let return_wrapper = RETURN_WRAPPERS
+        .iter()
+        .find(|(name, _)| c_name == name)
+        .map(|(_, wrapper)| format_ident!("{wrapper}"));
+
     // generate return type declaration for the outer function.
     let return_type = {
-        // collect arguments that are used for returning results
-        let return_args = func
-            .args
-            .iter()
-            .filter_map(|arg| match &arg.rs_arg_kind {
+        // collect arguments that are used for returning results,
+        // a boolean return value or status is returned in front of them
+        let return_bool = matches!(func.return_kind, ReturnKind::Bool | ReturnKind::Status)
+            .then(|| quote! { bool });
+        let return_args = return_bool
+            .into_iter()
+            .chain(func.args.iter().filter_map(|arg| match &arg.rs_arg_kind {
                 VmaVarKind::RefMut(ty) => Some(quote! { #ty }),
                 VmaVarKind::ArrayMut(ty, _) => Some(quote! { Vec<#ty> }),
                 VmaVarKind::StrMut => Some(quote! { Option<::std::ffi::CString> }),
                 _ => None,
-            })
+            }))
             .collect::<Vec<_>>();
-        let return_args_tuple = if return_args.is_empty() {
+        let return_args_tuple = if let Some(wrapper) = &return_wrapper {
+            quote! { crate::vma::#wrapper #lifetime }
+        } else if return_args.is_empty() {
             quote! {()}
         } else if return_args.len() == 1 {
             quote! { #(#return_args)* }
@@ -84,9 +116,9 @@ fn generate_func(func: VmaFunction) -> TokenStream {
             quote! { (#(#return_args),*) }
         };
 
-        // create final return type, either Result<..., vk::Result> or just the return_args
-        if func.returns_result {
-            quote! { -> Result<#return_args_tuple, vk::Result> }
+        // create final return type, either Result<..., vma::Error> or just the return_args
+        if func.return_kind.is_vk_result() {
+            quote! { -> Result<#return_args_tuple, crate::vma::Error> }
         } else if !return_args.is_empty() {
             quote! {
                 -> #return_args_tuple
@@ -96,11 +128,11 @@ fn generate_func(func: VmaFunction) -> TokenStream {
         }
     };
     // generate the low-level return type of the inner ffi function.
-    // either nothing or "-> vk::Result"
-    let c_return_type = if func.returns_result {
-        quote! { -> vk::Result }
-    } else {
-        quote! {}
+    // either nothing, "-> vk::Result" or "-> vk::Bool32"
+    let c_return_type = match func.return_kind {
+        ReturnKind::Void => quote! {},
+        ReturnKind::Result | ReturnKind::Status => quote! { -> vk::Result },
+        ReturnKind::Bool => quote! { -> vk::Bool32 },
     };
 
     // generate the temporary variables that will be passed for the return arguments
@@ -154,8 +186,8 @@ fn generate_func(func: VmaFunction) -> TokenStream {
 
     // generate code for passing the high-level arguments to the low-level ffi function
     let c_call = {
-        // if the ffi function returns a result, store it
-        let catch_result = if func.returns_result {
+        // if the ffi function returns a value, store it
+        let catch_result = if func.return_kind != ReturnKind::Void {
             quote! {let result = }
         } else {
             quote! {}
@@ -197,29 +229,38 @@ fn generate_func(func: VmaFunction) -> TokenStream {
                     quote! {#name}
                 }
                 VmaVarKind::RefMut(_) => quote! {&mut #name},
+                VmaVarKind::RefInOut(_) => quote! {#name},
                 VmaVarKind::ArrayMut(_, _) => quote! {#name.as_mut_ptr()},
                 VmaVarKind::Array(_, _) => quote! {#name.as_ptr()},
                 VmaVarKind::Str => quote! { #name.map_or(::std::ptr::null(), |s| s.as_ptr())  },
                 VmaVarKind::StrMut => quote! { &mut #name },
+                VmaVarKind::StrFree => quote! { #name },
                 VmaVarKind::ConstantArray(_, _) => panic!("Arrays not supported as arguments"),
                 VmaVarKind::BufferConst(_) => quote! { #name.as_ptr().cast() },
                 VmaVarKind::BufferMut(_) => quote! { #name.as_mut_ptr().cast() },
             }
         });
 
-        // final ffi function call
+        // final ffi function call, followed by raising assertions vma reported during the call
         quote! {
             #catch_result #c_name(#(#pass_args),*);
+            #[cfg(feature = "assert-hook")]
+            crate::assert::raise_pending();
         }
     };
 
     // generate the final high-level return statement if needed
     let return_statement = {
-        // collect all arguments used for returning results
-        let arg_returns = func
-            .args
-            .iter()
-            .filter_map(|arg| {
+        // collect all arguments used for returning results,
+        // a boolean return value or status is returned in front of them
+        let bool_return = match func.return_kind {
+            ReturnKind::Bool => Some(quote! { result != vk::FALSE }),
+            ReturnKind::Status => Some(quote! { result == vk::Result::INCOMPLETE }),
+            _ => None,
+        };
+        let arg_returns = bool_return
+            .into_iter()
+            .chain(func.args.iter().filter_map(|arg| {
                 let name = &arg.name;
                 match &arg.rs_arg_kind {
                     VmaVarKind::RefMut(_) | VmaVarKind::ArrayMut(_, _) => {
@@ -230,13 +271,15 @@ fn generate_func(func: VmaFunction) -> TokenStream {
                     }
                     _ => None,
                 }
-            })
+            }))
             .collect::<Vec<_>>();
 
-        let arg_returns_tuple = if arg_returns.len() == 1 {
+        let arg_returns_tuple = if return_wrapper.is_some() {
+            quote! { (#(#arg_returns),*).into() }
+        } else if arg_returns.len() == 1 {
             quote! { #(#arg_returns)* }
         } else if arg_returns.is_empty() {
-            if func.returns_result {
+            if func.return_kind.is_vk_result() {
                 quote! {()}
             } else {
                 quote! {}
@@ -245,13 +288,20 @@ fn generate_func(func: VmaFunction) -> TokenStream {
             quote! { (#(#arg_returns),*) }
         };
 
-        // return either a Result<..., vk::Result> or the return arguments directly
-        if func.returns_result {
+        // return either a Result<..., vma::Error> or the return arguments directly
+        if func.return_kind.is_vk_result() {
+            let c_name_str = c_name.to_string();
+            let context = func.args.iter().filter_map(error_context);
+            let success = if func.return_kind == ReturnKind::Status {
+                quote! { result == vk::Result::SUCCESS || result == vk::Result::INCOMPLETE }
+            } else {
+                quote! { result == vk::Result::SUCCESS }
+            };
             quote! {
-                if result == vk::Result::SUCCESS {
+                if #success {
                     Ok(#arg_returns_tuple)
                 } else {
-                    Err(result)
+                    Err(crate::vma::Error::new(#c_name_str, result)#(#context)*)
                 }
             }
         } else {
@@ -259,12 +309,6 @@ fn generate_func(func: VmaFunction) -> TokenStream {
         }
     };
 
-    let lifetime = if func.args.iter().any(|arg| arg.needs_lifetime) {
-        quote!{ <'a> }
-    } else {
-        quote!{}
-    };
-
     // final function definition
     quote! {
         #docs
@@ -280,6 +324,40 @@ fn generate_func(func: VmaFunction) -> TokenStream {
     }
 }
 
+/// Generates the code that attaches the request described by `arg` to a `vma::Error`.
+///
+/// Only arguments that describe what was requested (sizes, image properties, memory types, pools, usage)
+/// are recorded, all others return `None`.
+fn error_context(arg: &VmaFunctionArg) -> Option<TokenStream> {
+    let name = &arg.name;
+    match &arg.rs_arg_kind {
+        VmaVarKind::Ref(ty) => {
+            let method = match ty.to_token_stream().to_string().replace(' ', "").as_str() {
+                "crate::vma::AllocationCreateInfo" => quote! { with_allocation_create_info },
+                "crate::vma::PoolCreateInfo" => quote! { with_pool_create_info },
+                "crate::vma::VirtualAllocationCreateInfo" => {
+                    quote! { with_virtual_allocation_create_info }
+                }
+                "vk::MemoryRequirements" => quote! { with_memory_requirements },
+                "vk::BufferCreateInfo" => quote! { with_buffer_create_info },
+                "vk::ImageCreateInfo" => quote! { with_image_create_info },
+                _ => return None,
+            };
+            Some(quote! { .#method(#name) })
+        }
+        VmaVarKind::Normal => {
+            let method = match name.to_string().as_str() {
+                "size" => quote! { with_size },
+                "memory_type_bits" => quote! { with_memory_type_bits },
+                "pool" => quote! { with_pool },
+                _ => return None,
+            };
+            Some(quote! { .#method(#name) })
+        }
+        _ => None,
+    }
+}
+
 /// Parses the libclang definition of a vma function
 fn parse_function(entity: &Entity) -> VmaFunction {
     let name = entity.get_name().unwrap();
@@ -325,14 +403,37 @@ fn parse_function(entity: &Entity) -> VmaFunction {
         }
     }
 
-    let returns_result = entity.get_result_type().unwrap().get_kind() != TypeKind::Void;
+    // mark parameters that are read and written by vma
+    for (_, param) in IN_OUT_PARAMS.iter().filter(|(func, _)| *func == name) {
+        let arg = args
+            .iter_mut()
+            .find(|arg| arg.name == param.to_case(Case::Snake))
+            .unwrap_or_else(|| panic!("{name} has no parameter {param}"));
+        if let VmaVarKind::RefMut(ty) = &arg.rs_arg_kind {
+            arg.rs_arg_kind = VmaVarKind::RefInOut(ty.clone());
+        } else {
+            panic!("{name}::{param} is not a mutable reference");
+        }
+    }
+
+    // VkResult is turned into a Result<...>, VkBool32 into a bool
+    let result_type = entity.get_result_type().unwrap();
+    let return_kind = match result_type.get_kind() {
+        TypeKind::Void => ReturnKind::Void,
+        _ => match result_type.get_display_name().as_str() {
+            "VkResult" if INCOMPLETE_IS_SUCCESS.contains(&name.as_str()) => ReturnKind::Status,
+            "VkResult" => ReturnKind::Result,
+            "VkBool32" => ReturnKind::Bool,
+            other => panic!("Unsupported return type of {name}: {other}"),
+        },
+    };
 
     VmaFunction {
         c_name,
         rs_name,
         docs,
         args,
-        returns_result,
+        return_kind,
     }
 }
 
@@ -394,8 +495,28 @@ struct VmaFunction {
     docs: Option<LitStr>,
     /// list of arguments
     args: Vec<VmaFunctionArg>,
-    /// true if the ffi function returns a result
-    returns_result: bool,
+    /// what the ffi function returns
+    return_kind: ReturnKind,
+}
+
+/// Describes the return value of a vma function
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReturnKind {
+    /// Function returns nothing
+    Void,
+    /// Function returns a `VkResult`
+    Result,
+    /// Function returns a `VkBool32`
+    Bool,
+    /// Function returns a `VkResult` where `VK_INCOMPLETE` is not an error
+    Status,
+}
+
+impl ReturnKind {
+    /// Checks whether the ffi function returns a `VkResult`
+    fn is_vk_result(self) -> bool {
+        matches!(self, Self::Result | Self::Status)
+    }
 }
 
 /// Description of a vma function parameter
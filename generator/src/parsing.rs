@@ -14,6 +14,8 @@ pub enum VmaVarKind {
     Ref(syn::Type),
     /// A `*mut` to some type
     RefMut(syn::Type),
+    /// A `*mut` to some type that is read and written by vma
+    RefInOut(syn::Type),
     /// A `*const` to some type with a length
     Array(syn::Type, ArrayLen),
     /// A `*mut` to some type with a length
@@ -24,6 +26,8 @@ pub enum VmaVarKind {
     Str,
     /// A `*mut *const c_char`
     StrMut,
+    /// A `*mut c_char` that was allocated by VMA and is handed back to it to be freed
+    StrFree,
     /// A `*const c_void` or `*mut c_void` that extends a Vulkan structure
     PNext(syn::Type, bool),
     BufferConst(ArrayLen),
@@ -106,6 +110,8 @@ pub fn translate_var(
                         VmaVarKind::RefMut(converted_pointee)
                     }
                 }
+                // *mut c_char can not be used to return anything, it is a string previously returned by vma
+                (false, TypeKind::CharS | TypeKind::CharU, None) => VmaVarKind::StrFree,
                 (false, _, None) => VmaVarKind::RefMut(converted_pointee),
                 (true, TypeKind::Void, Some(len)) => VmaVarKind::BufferConst(len),
                 (true, TypeKind::Void, _) => {
@@ -22,7 +22,10 @@ pub fn generate_structs(tu: &Entity) -> TokenStream {
         }
     }
 
-    let generated = vma_structs.iter().map(generate_struct);
+    let serde_types = serde_types(&vma_structs, crate::enums::enum_names(tu));
+    let generated = vma_structs
+        .iter()
+        .map(|item| generate_struct(item, &serde_types));
 
     quote! {
         #(#generated)*
@@ -30,12 +33,18 @@ pub fn generate_structs(tu: &Entity) -> TokenStream {
 }
 
 /// Generates rust code for a parsed Vma struct
-fn generate_struct(item: &VmaStruct) -> TokenStream {
+fn generate_struct(item: &VmaStruct, serde_types: &[String]) -> TokenStream {
     let name = &item.name;
+    let is_serde = serde_types.contains(&name.to_string());
 
     // convert doc string to #[doc = "..."] attribute
     let docs = item.docs.as_ref().map(|docs| quote! { #[doc = #docs] });
 
+    // plain-data structs can optionally be (de)serialized
+    let serde_derive = is_serde.then(|| {
+        quote! { #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))] }
+    });
+
     // generate code for each field
     let fields = item.fields.iter().map(|field| {
         let name = &field.name;
@@ -43,8 +52,16 @@ fn generate_struct(item: &VmaStruct) -> TokenStream {
 
         let ty = &field.ty;
 
+        // user data pointers are meaningless outside of the process and are left out
+        let serde_skip = (is_serde
+            && matches!(serde_field(ty, serde_types), SerdeField::Skip))
+        .then(|| {
+            quote! { #[cfg_attr(feature = "serde", serde(skip, default = "::std::ptr::null_mut"))] }
+        });
+
         quote! {
             #docs
+            #serde_skip
             pub #name: #ty
         }
     });
@@ -58,6 +75,7 @@ fn generate_struct(item: &VmaStruct) -> TokenStream {
     quote! {
         #[repr(C)]
         #[derive(Debug, Clone, Copy)]
+        #serde_derive
         #docs
         pub struct #name #lifetime {
             #(#fields,)*
@@ -91,6 +109,84 @@ fn needs_lifetime(item: &VmaStruct) -> bool {
     })
 }
 
+/// How a struct field takes part in the optional serde derive
+enum SerdeField {
+    /// The field is (de)serialized
+    Plain,
+    /// The field is a `*mut c_void` that is skipped and deserialized as null
+    Skip,
+    /// The struct can not be (de)serialized because of this field
+    Unsupported,
+}
+
+/// Classifies a field type, `serde_types` are the names of all Vma types that can be (de)serialized
+fn serde_field(ty: &syn::Type, serde_types: &[String]) -> SerdeField {
+    match ty {
+        syn::Type::Array(array) => match serde_field(&array.elem, serde_types) {
+            SerdeField::Plain => SerdeField::Plain,
+            _ => SerdeField::Unsupported,
+        },
+        syn::Type::Ptr(ptr) => {
+            let pointee = &ptr.elem;
+            if ptr.mutability.is_some()
+                && quote! {#pointee}.to_string() == quote! {::std::ffi::c_void}.to_string()
+            {
+                SerdeField::Skip
+            } else {
+                SerdeField::Unsupported
+            }
+        }
+        syn::Type::Path(path) if path.path.segments.iter().all(|s| s.arguments.is_empty()) => {
+            let segments = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>();
+            let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+            match segments.as_slice() {
+                ["u32" | "i32" | "u64" | "i64" | "usize" | "f32"] => SerdeField::Plain,
+                ["vk", "DeviceSize" | "Bool32"] => SerdeField::Plain,
+                ["crate", "vma", name] if serde_types.iter().any(|ty| ty == name) => {
+                    SerdeField::Plain
+                }
+                _ => SerdeField::Unsupported,
+            }
+        }
+        _ => SerdeField::Unsupported,
+    }
+}
+
+/// Determines all Vma types that can be (de)serialized.
+///
+/// These are all enums and every struct without a lifetime whose fields are all plain data,
+/// with the exception of user data pointers.
+fn serde_types(structs: &[VmaStruct], enum_names: Vec<String>) -> Vec<String> {
+    let mut serde_types = enum_names;
+
+    // structs can contain other structs, so repeat until no more structs are found
+    loop {
+        let found = structs
+            .iter()
+            .filter(|item| !needs_lifetime(item) && !serde_types.contains(&item.name.to_string()))
+            .filter(|item| {
+                item.fields.iter().all(|field| {
+                    !matches!(
+                        serde_field(&field.ty, &serde_types),
+                        SerdeField::Unsupported
+                    )
+                })
+            })
+            .map(|item| item.name.to_string())
+            .collect::<Vec<_>>();
+
+        if found.is_empty() {
+            return serde_types;
+        }
+        serde_types.extend(found);
+    }
+}
+
 /// Generates getters for certain special fields
 /// - for array fields, generates getters returning a slice
 /// - for string fields, generates getters returning a &CStr